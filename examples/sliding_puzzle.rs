@@ -0,0 +1,80 @@
+//! Solves a scrambled 3×3 sliding puzzle, whose states are generated as the
+//! search comes across them.
+
+#![warn(
+	clippy::all,
+	// clippy::restriction,
+	clippy::pedantic,
+	clippy::nursery,
+	clippy::cargo
+)]
+// The crate is not published, so there is no metadata to speak of
+#![allow(clippy::cargo_common_metadata)]
+
+use a_star::lazy::{a_star_lazy, StateGraph};
+use a_star::Vertex2D;
+
+/// The tiles of the puzzle, row by row, `0` being the gap.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Board([u8; 9]);
+
+impl Vertex2D for Board {
+	fn coords(&self) -> (f64, f64) {
+		(0.0, 0.0)
+	}
+}
+
+/// The rules of the puzzle: a tile next to the gap may slide into it.
+struct Slides;
+
+impl StateGraph<Board> for Slides {
+	fn moves(&self, board: &Board) -> Vec<Board> {
+		let gap = board.0.iter().position(|&tile| tile == 0).unwrap();
+		let (x, y) = (gap % 3, gap / 3);
+		let mut tiles = Vec::with_capacity(4);
+		if x > 0 {
+			tiles.push(gap - 1);
+		}
+		if x < 2 {
+			tiles.push(gap + 1);
+		}
+		if y > 0 {
+			tiles.push(gap - 3);
+		}
+		if y < 2 {
+			tiles.push(gap + 3);
+		}
+		let slide = |tile| {
+			let mut next = board.clone();
+			next.0.swap(gap, tile);
+			next
+		};
+		tiles.into_iter().map(slide).collect()
+	}
+
+	/// Sums how far every tile is from where it belongs, which no single move
+	/// can bring down by more than one.
+	fn estimate(&self, board: &Board, goal: &Board) -> f64 {
+		let distance = |(index, &tile): (usize, &u8)| {
+			let target = goal.0.iter().position(|&other| other == tile).unwrap();
+			(index % 3).abs_diff(target % 3) + (index / 3).abs_diff(target / 3)
+		};
+		let tiles = board.0.iter().enumerate().filter(|&(_, &tile)| tile != 0);
+		#[allow(clippy::cast_precision_loss)]
+		let estimate = tiles.map(distance).sum::<usize>() as f64;
+		estimate
+	}
+}
+
+fn main() {
+	let scrambled = Board([8, 1, 3, 4, 0, 2, 7, 6, 5]);
+	let solved = Board([1, 2, 3, 4, 5, 6, 7, 8, 0]);
+	let solution = a_star_lazy(&Slides, scrambled, &solved).expect("the puzzle can't be solved");
+	println!("Solved in {} moves:", solution.len() - 1);
+	for board in solution {
+		for row in board.0.chunks(3) {
+			println!("{row:?}");
+		}
+		println!();
+	}
+}
@@ -0,0 +1,113 @@
+//! Caching of search results, for services answering the same queries over
+//! and over.
+
+use crate::compat::HashMap;
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::{a_star, Graph2D, Vertex2D};
+
+use core::cell::{Cell, RefCell};
+use core::hash::Hash;
+use core::ops::Deref;
+
+/// A cached path, along with when it was last looked up.
+struct Entry<V> {
+	path: Option<Vec<V>>,
+	last_used: u64,
+}
+
+/// A graph that remembers the paths found on it, up to a given amount of
+/// them, forgetting the least recently used ones first.
+///
+/// Paths are kept as owned copies of their vertices, so they don't borrow the
+/// graph. Since the cache can't tell when the graph changes, it has to be
+/// [invalidated](CachedPathfinder::invalidate) whenever it does.
+pub struct CachedPathfinder<G, V> {
+	graph: G,
+	capacity: usize,
+	cache: RefCell<HashMap<(V, V), Entry<V>>>,
+	clock: Cell<u64>,
+	hits: Cell<usize>,
+	misses: Cell<usize>,
+}
+
+impl<G, V> CachedPathfinder<G, V>
+where
+	G: Graph2D<V>,
+	V: Clone + Hash + Eq + Vertex2D,
+{
+	/// Wraps a graph, remembering up to `capacity` paths at once.
+	pub fn new(graph: G, capacity: usize) -> Self {
+		Self {
+			graph,
+			capacity,
+			cache: RefCell::new(HashMap::with_capacity(capacity)),
+			clock: Cell::new(0),
+			hits: Cell::new(0),
+			misses: Cell::new(0),
+		}
+	}
+
+	/// Same as [`a_star`], but the path is looked up in the cache first, and
+	/// only searched for if it isn't there. Queries without a path are cached
+	/// too.
+	pub fn path(&self, start: &V, goal: &V) -> Option<Vec<V>> {
+		let now = self.clock.get() + 1;
+		self.clock.set(now);
+		let mut cache = self.cache.borrow_mut();
+		let key = (start.clone(), goal.clone());
+		if let Some(entry) = cache.get_mut(&key) {
+			self.hits.set(self.hits.get() + 1);
+			entry.last_used = now;
+			return entry.path.clone();
+		}
+		self.misses.set(self.misses.get() + 1);
+		let path = a_star(&self.graph, start, goal).map(|path| path.into_iter().cloned().collect());
+		if self.capacity == 0 {
+			return path;
+		}
+		if cache.len() >= self.capacity {
+			let oldest = cache
+				.iter()
+				.min_by_key(|(_, entry)| entry.last_used)
+				.map(|(key, _)| key.clone());
+			if let Some(oldest) = oldest {
+				cache.remove(&oldest);
+			}
+		}
+		let entry = Entry {
+			path: path.clone(),
+			last_used: now,
+		};
+		cache.insert(key, entry);
+		path
+	}
+
+	/// Forgets every cached path, e.g., after the graph changed.
+	pub fn invalidate(&self) {
+		self.cache.borrow_mut().clear();
+	}
+
+	/// How many queries were answered from the cache.
+	pub const fn hits(&self) -> usize {
+		self.hits.get()
+	}
+
+	/// How many queries had to be searched for.
+	pub const fn misses(&self) -> usize {
+		self.misses.get()
+	}
+
+	/// Unwraps the graph, dropping the cache.
+	pub fn into_inner(self) -> G {
+		self.graph
+	}
+}
+
+impl<G, V> Deref for CachedPathfinder<G, V> {
+	type Target = G;
+
+	fn deref(&self) -> &G {
+		&self.graph
+	}
+}
@@ -0,0 +1,46 @@
+//! What the crate needs from the standard library, which is taken from
+//! `alloc`, `hashbrown` and `libm` instead when building without it.
+
+#[cfg(feature = "std")]
+pub use std::collections::{BinaryHeap, HashMap, HashSet};
+#[cfg(feature = "std")]
+pub use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+pub use alloc::{boxed::Box, collections::BinaryHeap, string::String, vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+pub use hashbrown::{HashMap, HashSet};
+
+/// The floating point functions the standard library provides, which `core`
+/// lacks. Only needs importing when the standard library isn't linked in at
+/// all, which it still is for unit tests.
+#[cfg(not(feature = "std"))]
+pub trait Float {
+	#[must_use]
+	fn atan2(self, other: Self) -> Self;
+	#[must_use]
+	fn floor(self) -> Self;
+	#[must_use]
+	fn hypot(self, other: Self) -> Self;
+	#[must_use]
+	fn mul_add(self, a: Self, b: Self) -> Self;
+}
+
+#[cfg(not(feature = "std"))]
+impl Float for f64 {
+	fn atan2(self, other: Self) -> Self {
+		libm::atan2(self, other)
+	}
+
+	fn floor(self) -> Self {
+		libm::floor(self)
+	}
+
+	fn hypot(self, other: Self) -> Self {
+		libm::hypot(self, other)
+	}
+
+	fn mul_add(self, a: Self, b: Self) -> Self {
+		libm::fma(self, a, b)
+	}
+}
@@ -0,0 +1,302 @@
+//! Changes to what moving around a graph costs, composed in one place rather
+//! than by nesting wrappers.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+use crate::compat::HashMap;
+#[cfg(not(feature = "std"))]
+use crate::compat::{Box, Vec};
+use crate::{run, Graph2D, Rules, Vertex2D};
+
+use core::hash::Hash;
+
+/// Changes the cost of a move from a vertex to another, given its cost so far.
+pub type Modifier<'g, V> = Box<dyn Fn(&V, &V, f64) -> f64 + 'g>;
+
+/// A graph whose travel costs go through a stack of modifiers, e.g., terrain
+/// multipliers, penalties or surcharges.
+///
+/// Each modifier is applied in turn to the cost the previous one returned,
+/// starting from the cost of the graph itself.
+///
+/// The heuristic of the graph is used as is, so it only stays admissible if
+/// no modified cost ever drops below what it assumes: modifiers that only ever
+/// add to or scale costs up are fine, while discounts of any kind call for a
+/// heuristic based on the cheapest modified cost instead.
+pub struct CostStack<'g, G, V> {
+	graph: &'g G,
+	modifiers: Vec<Modifier<'g, V>>,
+}
+
+impl<'g, G, V> CostStack<'g, G, V>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	pub const fn new(graph: &'g G) -> Self {
+		Self {
+			graph,
+			modifiers: Vec::new(),
+		}
+	}
+
+	/// Adds a modifier on top of the stack, to be applied after every other
+	/// one so far.
+	#[must_use]
+	pub fn with<F>(mut self, modifier: F) -> Self
+	where
+		F: Fn(&V, &V, f64) -> f64 + 'g,
+	{
+		self.modifiers.push(Box::new(modifier));
+		self
+	}
+
+	/// Runs a cost through every modifier, bottom to top.
+	fn modify(&self, vertex: &V, other: &V, cost: f64) -> f64 {
+		self.modifiers
+			.iter()
+			.fold(cost, |cost, modifier| modifier(vertex, other, cost))
+	}
+}
+
+impl<G, V> Graph2D<V> for CostStack<'_, G, V>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	crate::delegate_graph2d!(
+		graph => V: neighbors, path_is_transversable, has_vertex, heuristic, predecessors,
+		scale
+	);
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		self.modify(vertex, other, self.graph.travel_cost(vertex, other))
+	}
+
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		let cost = self.graph.travel_cost_with_context(previous, vertex, other);
+		self.modify(vertex, other, cost)
+	}
+}
+
+/// A graph in which moving along roads is discounted, so that searches
+/// prefer to stick to them even if that means going a little out of the way.
+///
+/// A move is on a road when both of its ends are, in which case its cost is
+/// multiplied by the discount, somewhere between `0` and `1`. Since the
+/// cheapest moves now cost that much less, the heuristic of the graph is
+/// scaled down by the discount too, which keeps it admissible if it was,
+/// although it guides searches away from roads less tightly.
+pub struct RoadNetwork<'g, G, R> {
+	graph: &'g G,
+	road: R,
+	discount: f64,
+}
+
+impl<'g, G, R> RoadNetwork<'g, G, R> {
+	pub const fn new(graph: &'g G, road: R, discount: f64) -> Self {
+		Self {
+			graph,
+			road,
+			discount,
+		}
+	}
+
+	/// Discounts the cost of a move if it is along a road.
+	fn discount<V>(&self, vertex: &V, other: &V, cost: f64) -> f64
+	where
+		R: Fn(&V) -> bool,
+	{
+		if (self.road)(vertex) && (self.road)(other) {
+			cost * self.discount
+		} else {
+			cost
+		}
+	}
+}
+
+impl<G, V, R> Graph2D<V> for RoadNetwork<'_, G, R>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+	R: Fn(&V) -> bool,
+{
+	crate::delegate_graph2d!(
+		graph => V: neighbors, path_is_transversable, has_vertex, predecessors, scale
+	);
+
+	fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+		self.graph.heuristic(vertex, other) * self.discount
+	}
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		self.discount(vertex, other, self.graph.travel_cost(vertex, other))
+	}
+
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		let cost = self.graph.travel_cost_with_context(previous, vertex, other);
+		self.discount(vertex, other, cost)
+	}
+}
+
+/// A graph that keeps track of how much traffic goes through each vertex, and
+/// makes moving into them costlier the busier they are.
+///
+/// Sequential searches then spread out instead of crowding the same corridors.
+/// Moving into a vertex costs `1 + weight · traffic` times as much as usual.
+/// Costs only ever go up, so an admissible heuristic stays admissible.
+pub struct TrafficGraph<'g, G, V> {
+	graph: &'g G,
+	weight: f64,
+	traffic: HashMap<&'g V, f64>,
+}
+
+impl<'g, G, V> TrafficGraph<'g, G, V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	pub fn new(graph: &'g G, weight: f64) -> Self {
+		Self {
+			graph,
+			weight,
+			traffic: HashMap::new(),
+		}
+	}
+
+	/// Counts a route as taken, adding one to the traffic of every vertex
+	/// along it but the first, which it only leaves.
+	pub fn record_path(&mut self, path: &[&'g V]) {
+		for &vertex in path.iter().skip(1) {
+			*self.traffic.entry(vertex).or_default() += 1.0;
+		}
+	}
+
+	/// Ages the traffic recorded so far, multiplying it by `factor`, e.g.,
+	/// once per tick, so that old routes weigh less than fresh ones.
+	pub fn decay(&mut self, factor: f64) {
+		for traffic in self.traffic.values_mut() {
+			*traffic *= factor;
+		}
+		self.traffic.retain(|_, traffic| *traffic > 0.0);
+	}
+
+	/// The traffic recorded through a vertex.
+	pub fn traffic(&self, vertex: &V) -> f64 {
+		self.traffic.get(vertex).copied().unwrap_or_default()
+	}
+
+	/// Inflates the cost of a move by the traffic at its destination.
+	fn congest(&self, other: &V, cost: f64) -> f64 {
+		cost * self.weight.mul_add(self.traffic(other), 1.0)
+	}
+}
+
+impl<G, V> Graph2D<V> for TrafficGraph<'_, G, V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	crate::delegate_graph2d!(
+		graph => V: neighbors, path_is_transversable, has_vertex, heuristic, predecessors,
+		scale
+	);
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		self.congest(other, self.graph.travel_cost(vertex, other))
+	}
+
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		let cost = self.graph.travel_cost_with_context(previous, vertex, other);
+		self.congest(other, cost)
+	}
+}
+
+/// A graph over terrain of varying height, in which climbing up costs extra,
+/// `weight` per unit of height gained, while heading down costs nothing more.
+///
+/// Costs only ever go up, so the heuristic of the graph stays admissible.
+pub struct ElevationGraph<'g, G, E> {
+	graph: &'g G,
+	height: E,
+	weight: f64,
+}
+
+impl<'g, G, E> ElevationGraph<'g, G, E> {
+	pub const fn new(graph: &'g G, height: E, weight: f64) -> Self {
+		Self {
+			graph,
+			height,
+			weight,
+		}
+	}
+
+	/// Adds the climb of a move to its cost.
+	fn climb<V>(&self, vertex: &V, other: &V, cost: f64) -> f64
+	where
+		E: Fn(&V) -> f64,
+	{
+		self.weight.mul_add(gain(&self.height, vertex, other), cost)
+	}
+}
+
+/// Returns how much height is gained moving from a vertex to another, which is
+/// nothing when heading down.
+fn gain<V, E: Fn(&V) -> f64>(height: &E, vertex: &V, other: &V) -> f64 {
+	(height(other) - height(vertex)).max(0.0)
+}
+
+/// Same as [`a_star`](crate::a_star), but over terrain as high as told by
+/// `height`, also returning the total height gained along the path.
+///
+/// Climbing is weighed against distance as in an [`ElevationGraph`], costing
+/// `weight` per unit of height gained on top of the cost of the graph, and
+/// only climbs count towards the height gained. Height and distance are seldom
+/// measured alike, so there is no telling how much a climb is worth without
+/// `weight`: `1.0` makes a unit of height cost as much as a unit of cost of the
+/// graph, and `0.0` finds the shortest path regardless of the climb.
+pub fn a_star_with_elevation<'m, G, V, E>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	height: E,
+	weight: f64,
+) -> Option<(Vec<&'m V>, f64)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	E: Fn(&V) -> f64,
+{
+	let climbing = |vertex: &V, other: &V| weight * gain(&height, vertex, other);
+	let rules = Rules {
+		penalty: Some(&climbing),
+		..Rules::default()
+	};
+	let path = run(map, &[(start, 0.0)], goal, &rules)?.path_to(goal);
+	let climb = path
+		.windows(2)
+		.map(|step| gain(&height, step[0], step[1]))
+		.sum();
+	Some((path, climb))
+}
+
+impl<G, V, E> Graph2D<V> for ElevationGraph<'_, G, E>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+	E: Fn(&V) -> f64,
+{
+	crate::delegate_graph2d!(
+		graph => V: neighbors, path_is_transversable, has_vertex, heuristic, predecessors,
+		scale
+	);
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		self.climb(vertex, other, self.graph.travel_cost(vertex, other))
+	}
+
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		let cost = self.graph.travel_cost_with_context(previous, vertex, other);
+		self.climb(vertex, other, cost)
+	}
+}
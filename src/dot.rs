@@ -0,0 +1,64 @@
+//! Exports of searches to Graphviz DOT, e.g., to render them with `dot`.
+
+use crate::compat::{HashMap, HashSet};
+#[cfg(not(feature = "std"))]
+use crate::compat::{String, Vec};
+use crate::search::cmp_coords;
+use crate::Vertex2D;
+
+use core::fmt::Write;
+use core::hash::{BuildHasher, Hash};
+
+/// The attributes of the vertices of the path, within their attribute list.
+const HIGHLIGHT_NODE: &str = " color=red penwidth=2";
+/// The attribute list of the edges of the path.
+const HIGHLIGHT_EDGE: &str = " [color=red penwidth=2]";
+
+/// Returns a DOT digraph of a search tree, as returned by
+/// [`a_star_with_tree`](crate::a_star_with_tree), highlighting `path` in it.
+///
+/// There is an edge from every vertex to each vertex reached through it.
+/// Vertices are identified and labeled by their coordinates, so vertices
+/// sharing them are drawn as one. Everything is listed in the order of their
+/// coordinates, so the output is the same for the same tree.
+pub fn export_search_dot<V, S>(tree: &HashMap<&V, &V, S>, path: &[&V]) -> String
+where
+	V: Hash + Eq + Vertex2D,
+	S: BuildHasher,
+{
+	let on_path: HashSet<_> = path.iter().copied().collect();
+	let path_edges: HashSet<_> = path.windows(2).map(|step| (step[0], step[1])).collect();
+	let mut nodes: Vec<&V> = tree
+		.iter()
+		.flat_map(|(&node, &parent)| [node, parent])
+		.collect();
+	nodes.extend(path);
+	nodes.sort_by(|node, other| cmp_coords(*node, *other));
+	nodes.dedup_by(|node, other| node.coords() == other.coords());
+	let mut edges: Vec<_> = tree.iter().map(|(&node, &parent)| (parent, node)).collect();
+	edges.sort_by(|(parent, node), (other_parent, other)| {
+		cmp_coords(*node, *other).then_with(|| cmp_coords(*parent, *other_parent))
+	});
+	let mut dot = String::from("digraph search {\n");
+	// Writing to a string never fails
+	for node in nodes {
+		let (x, y) = node.coords();
+		let style = if on_path.contains(node) {
+			HIGHLIGHT_NODE
+		} else {
+			""
+		};
+		let _ = writeln!(dot, "\t\"{x},{y}\" [label=\"({x}, {y})\"{style}];");
+	}
+	for (parent, node) in edges {
+		let ((x, y), (next_x, next_y)) = (parent.coords(), node.coords());
+		let style = if path_edges.contains(&(parent, node)) {
+			HIGHLIGHT_EDGE
+		} else {
+			""
+		};
+		let _ = writeln!(dot, "\t\"{x},{y}\" -> \"{next_x},{next_y}\"{style};");
+	}
+	dot.push_str("}\n");
+	dot
+}
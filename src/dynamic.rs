@@ -0,0 +1,241 @@
+//! Searches over graphs and vertices only known at runtime, e.g., picked from
+//! plugins, through object safe counterparts of [`Graph2D`] and [`Vertex2D`].
+//!
+//! Those traits can't be made into trait objects, since vertices are compared
+//! to, and taken by, their own type. Instead, any vertex that can be cloned,
+//! compared and hashed is a [`VertexDyn`], and any graph can be made into a
+//! [`Graph2DDyn`] through an [`Erased`] wrapper.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::{Box, Vec};
+use crate::{run, Graph2D, Rules, Vertex2D};
+
+use core::any::Any;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// An object safe [`Vertex2D`], which every vertex that can be cloned,
+/// compared and hashed already is.
+pub trait VertexDyn: 'static {
+	/// Same as [`Vertex2D::coords`].
+	fn coords(&self) -> (f64, f64);
+
+	/// Clones the vertex into a box of its own.
+	fn boxed_clone(&self) -> Box<dyn VertexDyn>;
+
+	/// Whether the vertex is the same as another, which it never is if they
+	/// aren't of the same type.
+	fn dyn_eq(&self, other: &dyn VertexDyn) -> bool;
+
+	/// Feeds the vertex into a hasher.
+	fn dyn_hash(&self, state: &mut dyn Hasher);
+
+	/// The vertex itself, to get its type back.
+	fn as_any(&self) -> &dyn Any;
+}
+
+impl<V> VertexDyn for V
+where
+	V: Clone + Hash + Eq + Vertex2D + 'static,
+{
+	fn coords(&self) -> (f64, f64) {
+		Vertex2D::coords(self)
+	}
+
+	fn boxed_clone(&self) -> Box<dyn VertexDyn> {
+		Box::new(self.clone())
+	}
+
+	fn dyn_eq(&self, other: &dyn VertexDyn) -> bool {
+		other.as_any().downcast_ref::<Self>() == Some(self)
+	}
+
+	fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+		self.hash(&mut state);
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+impl PartialEq for dyn VertexDyn + '_ {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other)
+	}
+}
+
+impl Eq for dyn VertexDyn + '_ {}
+
+impl Hash for dyn VertexDyn + '_ {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.dyn_hash(state);
+	}
+}
+
+impl Vertex2D for dyn VertexDyn + '_ {
+	fn coords(&self) -> (f64, f64) {
+		VertexDyn::coords(self)
+	}
+}
+
+/// An object safe [`Graph2D`], over vertices of any type.
+pub trait Graph2DDyn {
+	/// Same as [`Graph2D::neighbors`].
+	fn neighbors(&self, vertex: &dyn VertexDyn) -> Vec<&dyn VertexDyn>;
+
+	/// Same as [`Graph2D::path_is_transversable`].
+	fn path_is_transversable(&self, vertex: &dyn VertexDyn, other: &dyn VertexDyn) -> bool;
+
+	/// Same as [`Graph2D::has_vertex`].
+	fn has_vertex(&self, vertex: &dyn VertexDyn) -> bool;
+
+	/// Same as [`Graph2D::heuristic`].
+	fn heuristic(&self, vertex: &dyn VertexDyn, other: &dyn VertexDyn) -> f64;
+
+	/// Same as [`Graph2D::travel_cost`].
+	fn travel_cost(&self, vertex: &dyn VertexDyn, other: &dyn VertexDyn) -> f64;
+
+	/// Same as [`Graph2D::travel_cost_with_context`].
+	fn travel_cost_with_context(
+		&self,
+		previous: Option<&dyn VertexDyn>,
+		vertex: &dyn VertexDyn,
+		other: &dyn VertexDyn,
+	) -> f64 {
+		let _ = previous;
+		self.travel_cost(vertex, other)
+	}
+
+	/// Same as [`Graph2D::predecessors`].
+	fn predecessors(&self, vertex: &dyn VertexDyn) -> Vec<&dyn VertexDyn> {
+		self.neighbors(vertex)
+	}
+
+	/// Same as [`Graph2D::scale`].
+	fn scale(&self) -> (f64, f64) {
+		(1.0, 1.0)
+	}
+}
+
+/// A [`Graph2D`] made into a [`Graph2DDyn`].
+///
+/// Vertices of any other type than the graph's are not part of it, so they
+/// have no neighbors and can't be traveled to or from.
+pub struct Erased<G, V> {
+	graph: G,
+	vertex: PhantomData<fn(&V)>,
+}
+
+impl<G, V> Erased<G, V> {
+	pub const fn new(graph: G) -> Self {
+		Self {
+			graph,
+			vertex: PhantomData,
+		}
+	}
+
+	/// Unwraps the graph.
+	pub fn into_inner(self) -> G {
+		self.graph
+	}
+}
+
+/// Gets a vertex of the graph's type back, if it is one.
+fn concrete<V: 'static>(vertex: &dyn VertexDyn) -> Option<&V> {
+	vertex.as_any().downcast_ref()
+}
+
+impl<G, V> Graph2DDyn for Erased<G, V>
+where
+	G: Graph2D<V>,
+	V: Clone + Hash + Eq + Vertex2D + 'static,
+{
+	fn neighbors(&self, vertex: &dyn VertexDyn) -> Vec<&dyn VertexDyn> {
+		concrete(vertex).map_or_else(Vec::new, |vertex| {
+			let neighbors = self.graph.neighbors(vertex).into_iter();
+			neighbors
+				.map(|neighbor| neighbor as &dyn VertexDyn)
+				.collect()
+		})
+	}
+
+	fn path_is_transversable(&self, vertex: &dyn VertexDyn, other: &dyn VertexDyn) -> bool {
+		match (concrete(vertex), concrete(other)) {
+			(Some(vertex), Some(other)) => self.graph.path_is_transversable(vertex, other),
+			_ => false,
+		}
+	}
+
+	fn has_vertex(&self, vertex: &dyn VertexDyn) -> bool {
+		concrete(vertex).is_some_and(|vertex| self.graph.has_vertex(vertex))
+	}
+
+	fn heuristic(&self, vertex: &dyn VertexDyn, other: &dyn VertexDyn) -> f64 {
+		match (concrete(vertex), concrete(other)) {
+			(Some(vertex), Some(other)) => self.graph.heuristic(vertex, other),
+			_ => f64::INFINITY,
+		}
+	}
+
+	fn travel_cost(&self, vertex: &dyn VertexDyn, other: &dyn VertexDyn) -> f64 {
+		match (concrete(vertex), concrete(other)) {
+			(Some(vertex), Some(other)) => self.graph.travel_cost(vertex, other),
+			_ => f64::INFINITY,
+		}
+	}
+
+	fn travel_cost_with_context(
+		&self,
+		previous: Option<&dyn VertexDyn>,
+		vertex: &dyn VertexDyn,
+		other: &dyn VertexDyn,
+	) -> f64 {
+		let previous = previous.and_then(concrete);
+		match (concrete(vertex), concrete(other)) {
+			(Some(vertex), Some(other)) => {
+				self.graph.travel_cost_with_context(previous, vertex, other)
+			}
+			_ => f64::INFINITY,
+		}
+	}
+
+	fn predecessors(&self, vertex: &dyn VertexDyn) -> Vec<&dyn VertexDyn> {
+		concrete(vertex).map_or_else(Vec::new, |vertex| {
+			let predecessors = self.graph.predecessors(vertex).into_iter();
+			predecessors
+				.map(|predecessor| predecessor as &dyn VertexDyn)
+				.collect()
+		})
+	}
+
+	fn scale(&self) -> (f64, f64) {
+		self.graph.scale()
+	}
+}
+
+/// A [`Graph2DDyn`] made back into a [`Graph2D`], over vertices of any type,
+/// so that searches can run over it.
+struct Restored<'g> {
+	graph: &'g dyn Graph2DDyn,
+}
+
+impl Graph2D<dyn VertexDyn> for Restored<'_> {
+	crate::delegate_graph2d!(
+		graph => dyn VertexDyn: neighbors, path_is_transversable, has_vertex, heuristic,
+		travel_cost, travel_cost_with_context, predecessors, scale
+	);
+}
+
+/// Same as [`a_star`](crate::a_star), but over a graph only known at runtime,
+/// returning clones of the vertices of the path.
+pub fn a_star_dyn(
+	map: &dyn Graph2DDyn,
+	start: &dyn VertexDyn,
+	goal: &dyn VertexDyn,
+) -> Option<Vec<Box<dyn VertexDyn>>> {
+	let map = Restored { graph: map };
+	let search = run(&map, &[(start, 0.0)], goal, &Rules::default())?;
+	let path = search.path_to(goal);
+	Some(path.into_iter().map(VertexDyn::boxed_clone).collect())
+}
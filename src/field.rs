@@ -0,0 +1,182 @@
+//! Cost fields, i.e., the cost of reaching a goal from every vertex that can,
+//! e.g., to steer many agents towards it at once.
+
+use crate::compat::{HashMap, HashSet};
+use crate::search::Search;
+use crate::{neighbors, Graph2D, Vertex2D};
+
+use core::hash::Hash;
+
+/// Returns the cost of the cheapest path to `goal` from every vertex it can be
+/// reached from.
+///
+/// This is Dijkstra's algorithm run backwards from the goal, along the
+/// [`predecessors`](Graph2D::predecessors) of each vertex, so one-way edges
+/// are accounted for as long as the graph reports them.
+pub fn cost_field<'m, G, V>(map: &'m G, goal: &'m V) -> HashMap<&'m V, f64>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut field = HashMap::new();
+	let mut search = Search::with_capacity(0, 0);
+	search.use_heap();
+	search.open(goal, None, 0.0, 0.0);
+	while let Some(cur_node) = search.pop() {
+		// Nodes leave the open list in cost order, so their cost is final
+		field.insert(cur_node, search.g_score(cur_node));
+		for predecessor in map.predecessors(cur_node) {
+			if field.contains_key(predecessor) || !map.path_is_transversable(predecessor, cur_node)
+			{
+				continue;
+			}
+			search.relax(
+				cur_node,
+				predecessor,
+				map.travel_cost(predecessor, cur_node),
+				0.0,
+			);
+		}
+	}
+	field
+}
+
+/// A cost field that is kept up to date as vertices get blocked and unblocked,
+/// e.g., as obstacles come and go.
+///
+/// Only the costs a change affects are repaired, as in Lifelong Planning A*
+/// and D* Lite: every vertex also keeps the cost it should have per its
+/// neighbors, and only those for which both disagree are revisited, cheapest
+/// first, lowering or raising their cost and passing the change on.
+///
+/// Blocking a vertex only takes away the moves into and out of it, so unlike
+/// a wall on many grids, it doesn't keep diagonal moves from cutting its
+/// corners. Blocking the goal leaves nothing able to reach it.
+pub struct DynamicCostField<'m, G, V> {
+	map: &'m G,
+	goal: &'m V,
+	/// The cost of reaching the goal from each vertex, as far as it is known.
+	costs: HashMap<&'m V, f64>,
+	/// The cost each vertex should have, given the costs of its neighbors.
+	expected: HashMap<&'m V, f64>,
+	blocked: HashSet<&'m V>,
+	/// The vertices whose cost disagrees with what is expected of it, keyed
+	/// by the lowest of both.
+	queue: Search<&'m V>,
+	updates: usize,
+}
+
+impl<'m, G, V> DynamicCostField<'m, G, V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	/// Builds the cost field towards `goal`.
+	pub fn new(map: &'m G, goal: &'m V) -> Self {
+		let mut field = Self {
+			map,
+			goal,
+			costs: HashMap::new(),
+			expected: HashMap::new(),
+			blocked: HashSet::new(),
+			queue: Search::with_capacity(0, 0),
+			updates: 0,
+		};
+		field.queue.use_heap();
+		field.update(goal);
+		field.repair();
+		field
+	}
+
+	/// Returns the cost of reaching the goal from a vertex, or infinity if it
+	/// can't be reached.
+	pub fn cost(&self, vertex: &V) -> f64 {
+		self.costs.get(vertex).copied().unwrap_or(f64::INFINITY)
+	}
+
+	/// How many times the cost of a vertex was changed so far, building the
+	/// field included.
+	#[must_use]
+	pub const fn updates(&self) -> usize {
+		self.updates
+	}
+
+	/// Makes a vertex impassable, repairing the costs that went through it.
+	pub fn block(&mut self, vertex: &'m V) {
+		if self.blocked.insert(vertex) {
+			self.vertex_changed(vertex);
+		}
+	}
+
+	/// Makes a blocked vertex passable again, repairing the costs that can now
+	/// go through it.
+	pub fn unblock(&mut self, vertex: &'m V) {
+		if self.blocked.remove(vertex) {
+			self.vertex_changed(vertex);
+		}
+	}
+
+	/// Recomputes what is expected of a vertex and of everything leading to it,
+	/// then repairs whatever came out of line.
+	fn vertex_changed(&mut self, vertex: &'m V) {
+		self.update(vertex);
+		for predecessor in self.map.predecessors(vertex) {
+			self.update(predecessor);
+		}
+		self.repair();
+	}
+
+	/// Recomputes the cost expected of a vertex, queueing it up if its cost
+	/// disagrees.
+	#[allow(clippy::float_cmp)]
+	fn update(&mut self, vertex: &'m V) {
+		let expected = if self.blocked.contains(vertex) {
+			f64::INFINITY
+		} else if vertex == self.goal {
+			0.0
+		} else {
+			let through = neighbors(self.map, vertex).into_iter().filter(|&neighbor| {
+				!self.blocked.contains(neighbor) && self.map.path_is_transversable(vertex, neighbor)
+			});
+			through
+				.map(|neighbor| self.map.travel_cost(vertex, neighbor) + self.cost(neighbor))
+				.fold(f64::INFINITY, f64::min)
+		};
+		self.expected.insert(vertex, expected);
+		let (cost, expected) = (self.cost(vertex), self.expected(vertex));
+		if cost != expected {
+			let key = cost.min(expected);
+			self.queue.open(vertex, None, key, key);
+		}
+	}
+
+	/// Returns the cost expected of a vertex, or infinity if it was never
+	/// looked at.
+	fn expected(&self, vertex: &V) -> f64 {
+		self.expected.get(vertex).copied().unwrap_or(f64::INFINITY)
+	}
+
+	/// Settles every vertex whose cost disagrees with what is expected of it,
+	/// cheapest first, passing the changes on to the vertices leading to it.
+	#[allow(clippy::float_cmp)]
+	fn repair(&mut self) {
+		while let Some(vertex) = self.queue.pop() {
+			let (cost, expected) = (self.cost(vertex), self.expected(vertex));
+			// Vertices are left in the queue once they agree again
+			if cost == expected {
+				continue;
+			}
+			self.updates += 1;
+			if cost > expected {
+				self.costs.insert(vertex, expected);
+			} else {
+				// Too cheap, so start over from scratch
+				self.costs.remove(vertex);
+				self.update(vertex);
+			}
+			for predecessor in self.map.predecessors(vertex) {
+				self.update(predecessor);
+			}
+		}
+	}
+}
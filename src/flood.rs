@@ -0,0 +1,472 @@
+//! Searches that flood a graph outwards from a starting vertex, reaching every
+//! vertex around it by the cheapest path, instead of heading to a goal.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::compat::{HashMap, HashSet};
+use crate::search::{cmp_coords, Search};
+use crate::{costs_match, neighbors, Graph2D, Vertex2D};
+
+use core::cmp::Ordering;
+use core::hash::Hash;
+
+/// Returns every vertex that can be reached from `start` within a total cost
+/// of `max_cost`, along with the cost of the cheapest path to each of them.
+///
+/// This is Dijkstra's algorithm, i.e., A* without a heuristic, since there's
+/// no single goal to head to. The start itself is always reachable at no cost,
+/// unless `max_cost` is negative.
+pub fn reachable_within<'m, G, V>(map: &'m G, start: &'m V, max_cost: f64) -> HashMap<&'m V, f64>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	flood(map, start, max_cost, usize::MAX)
+		.into_iter()
+		.collect()
+}
+
+/// Returns the `k` vertices that are the cheapest to reach from `start`, the
+/// start included, sorted by the cost of the cheapest path to each of them.
+///
+/// The flood stops as soon as they are known, instead of reaching everything
+/// within some radius as [`reachable_within`] does. Fewer vertices are
+/// returned if no more can be reached. Ties at the last cost go to the
+/// smallest coordinates.
+pub fn nearest_reachable<'m, G, V>(map: &'m G, start: &'m V, k: usize) -> Vec<(&'m V, f64)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	flood(map, start, f64::INFINITY, k)
+}
+
+/// Floods the graph from `start` up to `max_cost`, returning at most
+/// `max_count` vertices along with their costs, in the order they were
+/// reached, i.e., from the cheapest to reach to the costliest.
+fn flood<'m, G, V>(map: &'m G, start: &'m V, max_cost: f64, max_count: usize) -> Vec<(&'m V, f64)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut reached = Vec::new();
+	if max_cost < 0.0 || max_count == 0 {
+		return reached;
+	}
+	let mut search = Search::with_capacity(0, 0);
+	search.use_heap();
+	search.open(start, None, 0.0, 0.0);
+	let mut finalized = HashSet::new();
+	while let Some(cur_node) = search.pop() {
+		// Nodes leave the open list in cost order, so their cost is final
+		let cur_g = search.g_score(cur_node);
+		finalized.insert(cur_node);
+		reached.push((cur_node, cur_g));
+		if reached.len() == max_count {
+			break;
+		}
+		let previous = search.info(cur_node).parent;
+		for neighbor in neighbors(map, cur_node) {
+			if finalized.contains(neighbor) || !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let cost = map.travel_cost_with_context(previous, cur_node, neighbor);
+			if cur_g + cost <= max_cost {
+				search.relax(cur_node, neighbor, cost, 0.0);
+			}
+		}
+	}
+	reached
+}
+
+/// The cost of the cheapest path between every pair of vertices of a small
+/// graph, as computed by [`all_pairs`].
+pub struct AllPairs<'m, V> {
+	costs: HashMap<(&'m V, &'m V), f64>,
+}
+
+impl<V: Hash + Eq> AllPairs<'_, V> {
+	/// Returns the cost of the cheapest path from one vertex to another, or
+	/// `None` if there is none, or either wasn't part of the precomputed ones.
+	pub fn query(&self, from: &V, to: &V) -> Option<f64> {
+		self.costs.get(&(from, to)).copied()
+	}
+}
+
+/// Precomputes the cost of the cheapest path between every pair of `nodes`,
+/// so that looking any of them up later on is immediate.
+///
+/// This floods the graph from every node, and keeps as many costs as there
+/// are pairs of nodes that can reach each other, i.e., up to `n²` of them, so
+/// it is only suited to small graphs, e.g., state machines or tiny maps.
+pub fn all_pairs<'m, G, V>(map: &'m G, nodes: &'m [V]) -> AllPairs<'m, V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut costs = HashMap::new();
+	for from in nodes {
+		let reachable = reachable_within(map, from, f64::INFINITY);
+		for to in nodes {
+			if let Some(&cost) = reachable.get(to) {
+				costs.insert((from, to), cost);
+			}
+		}
+	}
+	AllPairs { costs }
+}
+
+/// How the influences of several sources over a vertex are combined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Accumulate {
+	/// Only the strongest influence counts, as if the sources were one.
+	Maximum,
+	/// Every influence adds up.
+	Sum,
+}
+
+/// Floods the graph from several sources at once, e.g., for threat or
+/// attraction maps, and returns how much they influence each vertex.
+///
+/// Every source reaches the vertices within `max_cost` of it, influencing
+/// them by its weight, falling off linearly with the cost of the cheapest path
+/// to them, down to nothing at `max_cost`. Vertices reached by more than one
+/// source combine their influences as told by `accumulate`. This is a single
+/// Dijkstra flood seeded with every source, which keeps track of the source
+/// each path comes from.
+///
+/// With a `max_cost` of zero, there is nothing to fall off over, so the
+/// sources, and whatever they reach for free, are influenced by their full
+/// weight. A negative `max_cost` reaches nothing at all.
+pub fn influence_map<'m, G, V>(
+	map: &'m G,
+	sources: &[(&'m V, f64)],
+	max_cost: f64,
+	accumulate: Accumulate,
+) -> HashMap<&'m V, f64>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut search = Search::with_capacity(0, 0);
+	search.use_heap();
+	for (index, &(source, _)) in sources.iter().enumerate() {
+		search.open((source, index), None, 0.0, 0.0);
+	}
+	let mut influence = HashMap::new();
+	let mut finalized = HashSet::new();
+	while let Some(state) = search.pop() {
+		let ((cur_node, index), cur_g) = (state, search.g_score(state));
+		finalized.insert(state);
+		if cur_g > max_cost {
+			continue;
+		}
+		let falloff = if max_cost > 0.0 { 1.0 - cur_g / max_cost } else { 1.0 };
+		let contribution = sources[index].1 * falloff;
+		influence
+			.entry(cur_node)
+			.and_modify(|total: &mut f64| match accumulate {
+				Accumulate::Maximum => *total = total.max(contribution),
+				Accumulate::Sum => *total += contribution,
+			})
+			.or_insert(contribution);
+		let previous = search.info(state).parent.map(|(parent, _)| parent);
+		for neighbor in neighbors(map, cur_node) {
+			let next = (neighbor, index);
+			if finalized.contains(&next) || !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let g_score = cur_g + map.travel_cost_with_context(previous, cur_node, neighbor);
+			if g_score <= max_cost && g_score < search.g_score(next) {
+				search.open(next, Some(state), g_score, g_score);
+			}
+		}
+	}
+	influence
+}
+
+/// Splits the graph into the regions closest to each of several sources, by
+/// the cost of the cheapest path to them, e.g., to assign every cell to its
+/// nearest base.
+///
+/// Every vertex any source can reach is labelled with the index of the
+/// closest one, ties going to the lowest index. This is a Dijkstra flood
+/// starting from all sources at once, and the regions it spreads into are
+/// Voronoi regions along the graph rather than as the crow flies.
+pub fn multi_source_voronoi<'m, G, V>(map: &'m G, sources: &'m [V]) -> HashMap<&'m V, usize>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut search = Search::with_capacity(0, 0);
+	search.use_heap();
+	let mut labels = HashMap::new();
+	for (index, source) in sources.iter().enumerate() {
+		if !labels.contains_key(source) {
+			labels.insert(source, index);
+			search.open(source, None, 0.0, 0.0);
+		}
+	}
+	let mut finalized = HashSet::new();
+	while let Some(cur_node) = search.pop() {
+		finalized.insert(cur_node);
+		let (cur_g, label) = (search.g_score(cur_node), labels[cur_node]);
+		let previous = search.info(cur_node).parent;
+		for neighbor in neighbors(map, cur_node) {
+			if finalized.contains(neighbor) || !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let g_score = cur_g + map.travel_cost_with_context(previous, cur_node, neighbor);
+			let known = search.g_score(neighbor);
+			let is_tie = costs_match(g_score, known)
+				&& labels.get(neighbor).is_some_and(|&other| label < other);
+			if (g_score < known && !costs_match(g_score, known)) || is_tie {
+				search.open(neighbor, Some(cur_node), g_score, g_score);
+				labels.insert(neighbor, label);
+			}
+		}
+	}
+	labels
+}
+
+/// Returns a path from `start` leading at most `steps` away, towards the vertex
+/// that is the costliest for `threat` to reach, e.g., for an agent to run off.
+///
+/// Steps are hops between neighbors, whatever they cost, and the path takes
+/// as few of them as possible. Ties are broken by the vertex that takes the
+/// fewest steps to reach, then by the smallest coordinates.
+/// Vertices that `threat` can't reach at all are the best places to be, and
+/// `None` is returned if it can't reach the start either, as there is nothing
+/// to flee from then.
+pub fn flee_path<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	threat: &'m V,
+	steps: usize,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let danger = reachable_within(map, threat, f64::INFINITY);
+	if !danger.contains_key(start) {
+		return None;
+	}
+	let distance = |node: &V| danger.get(node).copied().unwrap_or(f64::INFINITY);
+	// Every step counts the same, so nodes are reached in the fewest of them
+	let mut search = Search::with_capacity(0, 0);
+	search.use_heap();
+	search.open(start, None, 0.0, 0.0);
+	let mut reached = Vec::new();
+	while let Some(cur_node) = search.pop() {
+		reached.push((cur_node, search.info(cur_node).depth));
+		if search.info(cur_node).depth >= steps {
+			continue;
+		}
+		for neighbor in neighbors(map, cur_node) {
+			if map.path_is_transversable(cur_node, neighbor) {
+				search.relax(cur_node, neighbor, 1.0, 0.0);
+			}
+		}
+	}
+	let (refuge, _) = reached
+		.into_iter()
+		.max_by(|&(node, hops), &(other, other_hops)| {
+			distance(node)
+				.partial_cmp(&distance(other))
+				.unwrap_or(Ordering::Equal)
+				.then_with(|| other_hops.cmp(&hops))
+				.then_with(|| cmp_coords(other, node))
+		})?;
+	Some(search.path_to(refuge))
+}
+
+/// Returns the vertex reachable from `start` within `max_cost` that is the
+/// cheapest to reach every other such vertex from, e.g., where to stand to get
+/// to the most things quickly.
+///
+/// This is the medoid of the reachable region: the vertex minimizing the total
+/// cost of the paths from it to every other reachable vertex. It is computed
+/// exactly, with a flood from every reachable vertex, so it is only suited to
+/// small regions. Ties are broken by the smallest coordinates.
+pub fn reachable_medoid<'m, G, V>(map: &'m G, start: &'m V, max_cost: f64) -> Option<&'m V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let region = reachable_within(map, start, max_cost);
+	// Any two vertices of the region are at most this far apart, going
+	// through the start
+	let diameter = 2.0 * max_cost;
+	region
+		.keys()
+		.map(|&candidate| {
+			let costs = reachable_within(map, candidate, diameter);
+			let total: f64 = region
+				.keys()
+				.map(|node| costs.get(node).copied().unwrap_or(f64::INFINITY))
+				.sum();
+			(candidate, total)
+		})
+		.min_by(|(node, total), (other, other_total)| {
+			total
+				.partial_cmp(other_total)
+				.unwrap_or(Ordering::Equal)
+				.then_with(|| cmp_coords(*node, *other))
+		})
+		.map(|(node, _)| node)
+}
+
+/// Returns how much clearance there is around every vertex of a path, i.e.,
+/// how far each of them lies from the closest obstacle, as the crow flies.
+///
+/// Obstacles are vertices that refuse every edge into them, e.g., walls, and
+/// the border of the graph, which lies a step beyond the vertices that have
+/// fewer neighbors than the most connected ones. They are all found in the
+/// region linked to the path, regardless of whether its edges can be taken,
+/// and flooded outwards from at once. Distances are between vertices, so the
+/// clearance next to a wall is a whole step, and it is infinite where no
+/// obstacle can be found at all.
+pub fn path_clearance<G, V>(map: &G, path: &[&V]) -> Vec<f64>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let clearance = clearances(map, path);
+	path.iter()
+		.map(|vertex| clearance.g_score(vertex))
+		.collect()
+}
+
+/// Returns whether an agent of a given radius can follow a path without ever
+/// running into an obstacle, as told by [`path_clearance`].
+pub fn path_fits<G, V>(map: &G, path: &[&V], radius: f64) -> bool
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	path_clearance(map, path)
+		.into_iter()
+		.all(|clearance| clearance >= radius)
+}
+
+/// Floods the region linked to a path from all of its obstacles at once,
+/// scoring every vertex by its distance to the closest of them.
+fn clearances<'m, G, V>(map: &'m G, path: &[&'m V]) -> Search<&'m V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut region: HashSet<&V> = path.iter().copied().collect();
+	let mut pending = path.to_vec();
+	while let Some(node) = pending.pop() {
+		for neighbor in neighbors(map, node) {
+			if region.insert(neighbor) {
+				pending.push(neighbor);
+			}
+		}
+	}
+	let degree = |node| neighbors(map, node).len();
+	let max_degree = region.iter().map(|&node| degree(node)).max().unwrap_or(0);
+	let mut search = Search::with_capacity(region.len(), 0);
+	search.use_heap();
+	// Every vertex is as far as the obstacle it was reached from, plus how
+	// far beyond that obstacle's vertex the obstacle itself lies
+	let mut closest = HashMap::new();
+	for &node in &region {
+		let neighbors = neighbors(map, node);
+		let beyond = if neighbors
+			.iter()
+			.all(|&neighbor| !map.path_is_transversable(neighbor, node))
+		{
+			0.0
+		} else if neighbors.len() < max_degree {
+			let distances = neighbors
+				.iter()
+				.map(|&neighbor| node.euclidean_distance(neighbor));
+			distances.fold(f64::INFINITY, f64::min)
+		} else {
+			continue;
+		};
+		search.open(node, None, beyond, beyond);
+		closest.insert(node, (node, beyond));
+	}
+	let mut finalized = HashSet::new();
+	while let Some(cur_node) = search.pop() {
+		finalized.insert(cur_node);
+		let (obstacle, beyond) = closest[cur_node];
+		for neighbor in neighbors(map, cur_node) {
+			if finalized.contains(neighbor) {
+				continue;
+			}
+			let distance = neighbor.euclidean_distance(obstacle) + beyond;
+			if distance < search.g_score(neighbor) {
+				search.open(neighbor, Some(cur_node), distance, distance);
+				closest.insert(neighbor, (obstacle, beyond));
+			}
+		}
+	}
+	search
+}
+
+/// Returns the convex hull of every vertex that can be reached from `start`
+/// within `max_cost`, e.g., to outline the area an agent can get to.
+///
+/// The hull is given as the coordinates of its corners, counterclockwise in a
+/// y-up frame, starting from the one with the smallest coordinates. Vertices
+/// along its edges are not corners, so a region that is a line only has its
+/// two ends, and a single vertex is a hull of its own.
+pub fn reachable_hull<G, V>(map: &G, start: &V, max_cost: f64) -> Vec<(f64, f64)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut points: Vec<_> = flood(map, start, max_cost, usize::MAX)
+		.into_iter()
+		.map(|(node, _)| node.coords())
+		.collect();
+	points.sort_by(|&(x, y), &(other_x, other_y)| {
+		x.partial_cmp(&other_x)
+			.unwrap_or(Ordering::Equal)
+			.then_with(|| y.partial_cmp(&other_y).unwrap_or(Ordering::Equal))
+	});
+	points.dedup();
+	if points.len() < 3 {
+		return points;
+	}
+	// Andrew's monotone chain: the lower half of the hull from left to right,
+	// then the upper half back, each of them ending where the other starts
+	let mut hull = Vec::with_capacity(2 * points.len());
+	extend_hull(&mut hull, points.iter().copied());
+	hull.pop();
+	extend_hull(&mut hull, points.iter().rev().copied());
+	hull.pop();
+	hull
+}
+
+/// Adds half of a convex hull to `hull`, given points sorted along it, only
+/// keeping those the chain turns left at.
+fn extend_hull(hull: &mut Vec<(f64, f64)>, points: impl Iterator<Item = (f64, f64)>) {
+	let bottom = hull.len();
+	for point in points {
+		while hull.len() >= bottom + 2
+			&& cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0
+		{
+			hull.pop();
+		}
+		hull.push(point);
+	}
+}
+
+/// Returns the z component of the cross product of `origin`-`point` and
+/// `origin`-`other`, which is positive if going from one to the other turns
+/// left.
+fn cross(
+	(x, y): (f64, f64),
+	(point_x, point_y): (f64, f64),
+	(other_x, other_y): (f64, f64),
+) -> f64 {
+	(point_x - x).mul_add(other_y - y, -(point_y - y) * (other_x - x))
+}
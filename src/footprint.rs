@@ -0,0 +1,109 @@
+//! Graphs searched on behalf of agents that take up more than a single vertex.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::{vec, Vec};
+use crate::{Graph2D, Vertex2D};
+
+/// A graph searched for agents of a given footprint, `(width, height)` vertices
+/// wide, e.g., units too bulky to squeeze through gaps a single one wide.
+///
+/// Coordinates are expected to count whole vertices, so that every vertex
+/// stands for the corner of the agent with the smallest coordinates, the rest
+/// of it covering the vertices up to `width - 1` along x and `height - 1`
+/// along y from there. The agent fits where every vertex it covers can be
+/// reached from every other without leaving the footprint, so walls refusing
+/// every edge into them keep it out, and it may only move if each of those
+/// vertices can make the same move. Costs and the heuristic are left to the
+/// graph, as if the agent were the corner it is tracked by.
+pub struct FootprintGraph<'g, G> {
+	graph: &'g G,
+	footprint: (usize, usize),
+}
+
+impl<'g, G> FootprintGraph<'g, G> {
+	pub const fn new(graph: &'g G, footprint: (usize, usize)) -> Self {
+		Self { graph, footprint }
+	}
+
+	/// Returns every vertex covered by the agent with its corner at `vertex`,
+	/// `vertex` first, if it fits there.
+	fn cover<'v, V>(&'v self, vertex: &'v V) -> Option<Vec<&'v V>>
+	where
+		G: Graph2D<V>,
+		V: PartialEq + Vertex2D,
+	{
+		let (width, height) = self.footprint;
+		let (x, y) = vertex.coords();
+		#[allow(clippy::cast_precision_loss)]
+		let (right, bottom) = (x + width as f64 - 1.0, y + height as f64 - 1.0);
+		let covers = |other: &V| {
+			let (other_x, other_y) = other.coords();
+			(x - 1e-9..=right + 1e-9).contains(&other_x)
+				&& (y - 1e-9..=bottom + 1e-9).contains(&other_y)
+		};
+		let mut cover = vec![vertex];
+		let mut index = 0;
+		while let Some(&cur_node) = cover.get(index) {
+			for neighbor in self.graph.neighbors(cur_node) {
+				if covers(neighbor)
+					&& !cover.contains(&neighbor)
+					&& self.graph.path_is_transversable(cur_node, neighbor)
+				{
+					cover.push(neighbor);
+				}
+			}
+			index += 1;
+		}
+		(cover.len() == width * height).then_some(cover)
+	}
+
+	/// Returns whether the agent fits at `other`, and every vertex it covers
+	/// at `vertex` can make the move there.
+	fn can_move<V>(&self, vertex: &V, other: &V) -> bool
+	where
+		G: Graph2D<V>,
+		V: PartialEq + Vertex2D,
+	{
+		let (Some(from), Some(to)) = (self.cover(vertex), self.cover(other)) else {
+			return false;
+		};
+		let ((x, y), (other_x, other_y)) = (vertex.coords(), other.coords());
+		let (dx, dy) = (other_x - x, other_y - y);
+		from.into_iter().all(|cell| {
+			let (cell_x, cell_y) = cell.coords();
+			let moved = to.iter().find(|moved| {
+				let (moved_x, moved_y) = moved.coords();
+				(moved_x - cell_x - dx).abs() <= 1e-9 && (moved_y - cell_y - dy).abs() <= 1e-9
+			});
+			moved.is_some_and(|moved| self.graph.path_is_transversable(cell, moved))
+		})
+	}
+}
+
+impl<G, V> Graph2D<V> for FootprintGraph<'_, G>
+where
+	G: Graph2D<V>,
+	V: PartialEq + Vertex2D,
+{
+	crate::delegate_graph2d!(
+		graph => V: has_vertex, heuristic, travel_cost, travel_cost_with_context, scale
+	);
+
+	fn neighbors(&self, vertex: &V) -> Vec<&V> {
+		let neighbors = self.graph.neighbors(vertex).into_iter();
+		neighbors
+			.filter(|neighbor| self.cover(*neighbor).is_some())
+			.collect()
+	}
+
+	fn path_is_transversable(&self, vertex: &V, other: &V) -> bool {
+		self.graph.path_is_transversable(vertex, other) && self.can_move(vertex, other)
+	}
+
+	fn predecessors(&self, vertex: &V) -> Vec<&V> {
+		let predecessors = self.graph.predecessors(vertex).into_iter();
+		predecessors
+			.filter(|predecessor| self.cover(*predecessor).is_some())
+			.collect()
+	}
+}
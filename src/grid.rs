@@ -0,0 +1,221 @@
+//! Helpers for implementing [`Graph2D`](crate::Graph2D) on square grids whose
+//! cells are addressed by `(x, y)` coordinates, with `(0, 0)` at a corner.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use core::convert::TryFrom;
+
+/// Offsets of the 8 cells surrounding a cell, shifted by one so they can be
+/// applied to unsigned coordinates, in row-major order.
+const OFFSETS_8: [(usize, usize); 8] = [
+	(0, 0),
+	(1, 0),
+	(2, 0),
+	(0, 1),
+	(2, 1),
+	(0, 2),
+	(1, 2),
+	(2, 2),
+];
+
+/// Offsets of the 4 cells orthogonally adjacent to a cell, shifted by one so
+/// they can be applied to unsigned coordinates, in row-major order.
+const OFFSETS_4: [(usize, usize); 4] = [(1, 0), (0, 1), (2, 1), (1, 2)];
+
+/// Returns the coordinates of every cell surrounding `from`, including the
+/// diagonals, that lie within a grid of the given dimensions.
+#[must_use]
+pub fn grid_neighbors_8(from: (usize, usize), width: usize, height: usize) -> Vec<(usize, usize)> {
+	neighbors_with(&OFFSETS_8, from, width, height)
+}
+
+/// Returns the coordinates of the cells orthogonally adjacent to `from` that
+/// lie within a grid of the given dimensions.
+#[must_use]
+pub fn grid_neighbors_4(from: (usize, usize), width: usize, height: usize) -> Vec<(usize, usize)> {
+	neighbors_with(&OFFSETS_4, from, width, height)
+}
+
+fn neighbors_with(
+	offsets: &[(usize, usize)],
+	(x, y): (usize, usize),
+	width: usize,
+	height: usize,
+) -> Vec<(usize, usize)> {
+	// Shifting the coordinates up before down avoids underflowing at the
+	// lower boundaries, and overflowing at the upper ones is likewise checked
+	let resolve = |axis: usize, delta: usize, len: usize| {
+		axis.checked_add(delta)
+			.and_then(|coord| coord.checked_sub(1))
+			.filter(|&coord| coord < len)
+	};
+	offsets
+		.iter()
+		.filter_map(|&(dx, dy)| Some((resolve(x, dx, width)?, resolve(y, dy, height)?)))
+		.collect()
+}
+
+/// Returns the signed offset between two coordinates of an axis, if it fits.
+fn delta(from: usize, to: usize) -> Option<i64> {
+	if to >= from {
+		i64::try_from(to - from).ok()
+	} else {
+		i64::try_from(from - to).ok().map(|delta| -delta)
+	}
+}
+
+/// Moves a coordinate of an axis by a signed offset, returning `None` if that
+/// falls outside of the `usize` range.
+fn shift(axis: usize, delta: i64) -> Option<usize> {
+	let distance = usize::try_from(delta.unsigned_abs()).ok()?;
+	if delta >= 0 {
+		axis.checked_add(distance)
+	} else {
+		axis.checked_sub(distance)
+	}
+}
+
+/// Moves a cell by a signed `(dx, dy)` offset, returning `None` if the result
+/// cannot be addressed.
+#[must_use]
+pub fn offset((x, y): (usize, usize), (dx, dy): (i64, i64)) -> Option<(usize, usize)> {
+	Some((shift(x, dx)?, shift(y, dy)?))
+}
+
+/// What diagonal steps may do about the two cells orthogonally adjacent to
+/// both of their ends, i.e., the corners they cut.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CornerPolicy {
+	/// Diagonal steps may cut any corner, even squeezing in between two walls.
+	Allow,
+	/// Diagonal steps may cut a corner as long as the other one is free, so
+	/// walls can't be crossed through their corners.
+	DisallowBoth,
+	/// Diagonal steps may only be taken if both corners are free.
+	DisallowEither,
+}
+
+/// Determines whether a single step between two cells is possible regarding
+/// the cells around them, where `is_free` tells whether a cell can be walked
+/// on.
+///
+/// Whether either end of the step is free itself is left to the caller.
+/// Staying in place and moving orthogonally are always possible, while moving
+/// diagonally is only possible if at least one of the two cells orthogonally
+/// adjacent to both ends is free, so walls can't be crossed through their
+/// corners. Cells further than one step apart are never reachable.
+pub fn step_is_clear<F>(from: (usize, usize), to: (usize, usize), is_free: F) -> bool
+where
+	F: Fn(usize, usize) -> bool,
+{
+	step_is_clear_with_policy(from, to, is_free, CornerPolicy::DisallowBoth)
+}
+
+/// Same as [`step_is_clear`], but diagonal steps follow the given policy for
+/// the corners they cut.
+pub fn step_is_clear_with_policy<F>(
+	from: (usize, usize),
+	to: (usize, usize),
+	is_free: F,
+	policy: CornerPolicy,
+) -> bool
+where
+	F: Fn(usize, usize) -> bool,
+{
+	let (Some(dx), Some(dy)) = (delta(from.0, to.0), delta(from.1, to.1)) else {
+		return false;
+	};
+	match (dx.abs(), dy.abs()) {
+		(0, 0 | 1) | (1, 0) => true,
+		(1, 1) => {
+			// Both corners are in bounds, since they share their axes with
+			// either end of the step
+			let is_corner_free =
+				|corner: Option<(usize, usize)>| corner.is_some_and(|(x, y)| is_free(x, y));
+			match policy {
+				CornerPolicy::Allow => true,
+				CornerPolicy::DisallowBoth => {
+					is_corner_free(offset(from, (0, dy))) || is_corner_free(offset(from, (dx, 0)))
+				}
+				CornerPolicy::DisallowEither => {
+					is_corner_free(offset(from, (0, dy))) && is_corner_free(offset(from, (dx, 0)))
+				}
+			}
+		}
+		_ => false,
+	}
+}
+
+/// Determines whether a path of cells, e.g., a cached one, can still be
+/// walked, with every cell along it free and every step clear.
+///
+/// The policy should be the one of the search that found the path, or paths
+/// cutting corners it allowed would be rejected.
+pub fn validate_path<F>(path: &[(usize, usize)], is_free: F, policy: CornerPolicy) -> bool
+where
+	F: Fn(usize, usize) -> bool,
+{
+	path.iter().all(|&(x, y)| is_free(x, y))
+		&& path
+			.windows(2)
+			.all(|step| step_is_clear_with_policy(step[0], step[1], &is_free, policy))
+}
+
+/// Where a grid lies in a continuous world, so that agents moving about it
+/// freely can be pathed on it.
+///
+/// A frame is given by the world position of the outer corner of the cell at
+/// `(0, 0)`, how large cells are, and how many there are along each axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridFrame {
+	origin: (f64, f64),
+	cell_size: f64,
+	width: usize,
+	height: usize,
+}
+
+impl GridFrame {
+	#[must_use]
+	pub const fn new(origin: (f64, f64), cell_size: f64, width: usize, height: usize) -> Self {
+		Self {
+			origin,
+			cell_size,
+			width,
+			height,
+		}
+	}
+
+	/// Returns the cell a world position falls in.
+	///
+	/// Positions outside the grid are clamped to the closest cell along its
+	/// edge, so an agent that strayed off it still paths from where it left.
+	/// Positions on the border between two cells belong to the one further
+	/// from the origin.
+	#[must_use]
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		clippy::cast_precision_loss
+	)]
+	pub fn to_cell(&self, (x, y): (f64, f64)) -> (usize, usize) {
+		let snap = |coord: f64, origin: f64, len: usize| {
+			let cell = ((coord - origin) / self.cell_size).floor();
+			// Negative and undefined positions clamp to the first cell
+			cell.max(0.0).min(len.saturating_sub(1) as f64) as usize
+		};
+		(
+			snap(x, self.origin.0, self.width),
+			snap(y, self.origin.1, self.height),
+		)
+	}
+
+	/// Returns the world position of the center of a cell.
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub fn to_world(&self, (x, y): (usize, usize)) -> (f64, f64) {
+		let center = |cell: usize, origin: f64| (cell as f64 + 0.5).mul_add(self.cell_size, origin);
+		(center(x, self.origin.0), center(y, self.origin.1))
+	}
+}
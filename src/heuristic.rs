@@ -0,0 +1,287 @@
+//! Heuristics known never to overestimate the cost of reaching the goal on
+//! some kinds of graphs, so that searches using them find optimal paths, and
+//! checks for custom ones.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+#[cfg(not(feature = "std"))]
+use crate::compat::{Box, Vec};
+use crate::{Graph2D, Vertex2D, COST_TOLERANCE};
+
+use core::f64::consts::SQRT_2;
+use core::fmt::Debug;
+
+/// A heuristic that is admissible for the kind of graph it was picked for.
+///
+/// The type can only be built by choosing one of the known heuristics, each
+/// documenting the graphs it suits, so it is a reminder of the optimality
+/// guarantee it comes with:
+///
+/// ```compile_fail
+/// # use a_star::heuristic::Admissible;
+/// let guess = Admissible(|_: &(), _: &()| 0.0);
+/// ```
+///
+/// Any other heuristic has to be vouched for explicitly:
+///
+/// ```
+/// # use a_star::heuristic::Admissible;
+/// let guess = Admissible::new_unchecked(|_: &(), _: &()| 0.0);
+/// ```
+pub struct Admissible<H>(H);
+
+impl<V: Vertex2D> Admissible<fn(&V, &V) -> f64> {
+	/// Never estimates anything, and is thus always admissible, though the
+	/// search degrades into Dijkstra's algorithm.
+	#[must_use]
+	pub fn zero() -> Self {
+		Self(|_, _| 0.0)
+	}
+
+	/// The [manhattan distance](Vertex2D::manhattan_distance), for
+	/// 4-connected grids whose steps cost at least their length.
+	#[must_use]
+	pub fn manhattan() -> Self {
+		Self(V::manhattan_distance)
+	}
+
+	/// The [chebyshev distance](Vertex2D::chebyshev_distance), for
+	/// 8-connected grids whose steps, diagonal or not, cost at least one.
+	#[must_use]
+	pub fn chebyshev() -> Self {
+		Self(V::chebyshev_distance)
+	}
+
+	/// The length of the shortest route made of orthogonal and diagonal
+	/// steps, for 8-connected grids charging at least the euclidean length
+	/// of each step.
+	#[must_use]
+	pub fn octile() -> Self {
+		Self(|vertex, other| vertex.octile_distance_with(other, SQRT_2, 1.0))
+	}
+
+	/// The [euclidean distance](Vertex2D::euclidean_distance), for any
+	/// graph whose edges cost at least the straight line between their
+	/// vertices.
+	#[must_use]
+	pub fn euclidean() -> Self {
+		Self(V::euclidean_distance)
+	}
+}
+
+impl<H> Admissible<H> {
+	/// Takes the caller's word for the heuristic being admissible. Searches
+	/// are only as optimal as it really is.
+	pub const fn new_unchecked(heuristic: H) -> Self {
+		Self(heuristic)
+	}
+
+	/// Estimates the cost of reaching the goal from a vertex.
+	pub fn estimate<V>(&self, vertex: &V, goal: &V) -> f64
+	where
+		H: Fn(&V, &V) -> f64,
+	{
+		(self.0)(vertex, goal)
+	}
+}
+
+/// A heuristic of any kind.
+type Heuristic<'h, V> = Box<dyn Fn(&V, &V) -> f64 + 'h>;
+
+/// The pointwise maximum of several admissible heuristics, e.g., a distance
+/// along with a bound known from elsewhere, which is tighter than any of them.
+///
+/// If none of the heuristics ever overestimates the cost of reaching the goal,
+/// their maximum, being one of them at every vertex, doesn't either, so it is
+/// still admissible. Likewise, if they are all consistent, so is their
+/// maximum, and only then are searches using it sure to expand no more
+/// vertices than with any of them, ties aside: an inconsistent heuristic may
+/// have vertices expanded over again, however much tighter it is. Without any
+/// heuristic, nothing is ever estimated.
+pub struct MaxHeuristic<'h, V> {
+	heuristics: Vec<Heuristic<'h, V>>,
+}
+
+impl<'h, V> MaxHeuristic<'h, V> {
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			heuristics: Vec::new(),
+		}
+	}
+
+	/// Adds another heuristic to take the maximum of.
+	#[must_use]
+	pub fn with<H>(mut self, heuristic: Admissible<H>) -> Self
+	where
+		H: Fn(&V, &V) -> f64 + 'h,
+	{
+		self.heuristics.push(Box::new(heuristic.0));
+		self
+	}
+
+	/// Estimates the cost of reaching the goal from a vertex, as the highest
+	/// estimate of all the heuristics.
+	pub fn estimate(&self, vertex: &V, goal: &V) -> f64 {
+		let estimates = self
+			.heuristics
+			.iter()
+			.map(|heuristic| heuristic(vertex, goal));
+		estimates.fold(0.0, f64::max)
+	}
+
+	/// Turns the maximum into a heuristic of its own, e.g., for
+	/// [`a_star_admissible`](crate::a_star_admissible).
+	#[must_use]
+	pub fn into_admissible(self) -> Admissible<impl Fn(&V, &V) -> f64 + 'h>
+	where
+		V: 'h,
+	{
+		Admissible(move |vertex: &V, goal: &V| self.estimate(vertex, goal))
+	}
+}
+
+impl<V> Default for MaxHeuristic<'_, V> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Returns the axial distances between two vertices, stretched by the
+/// [`scale`](Graph2D::scale) of the graph.
+fn scaled_distances<G, V>(map: &G, vertex: &V, other: &V) -> (f64, f64)
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	let (x, y) = vertex.coords();
+	let (other_x, other_y) = other.coords();
+	let (x_scale, y_scale) = map.scale();
+	((other_x - x).abs() * x_scale, (other_y - y).abs() * y_scale)
+}
+
+/// Same as [`Vertex2D::euclidean_distance`], but along the axes of the graph,
+/// stretched by its [`scale`](Graph2D::scale).
+pub fn euclidean<G, V>(map: &G, vertex: &V, other: &V) -> f64
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	let (x_dist, y_dist) = scaled_distances(map, vertex, other);
+	x_dist.hypot(y_dist)
+}
+
+/// Same as [`Vertex2D::chebyshev_distance`], but along the axes of the graph,
+/// stretched by its [`scale`](Graph2D::scale).
+pub fn chebyshev<G, V>(map: &G, vertex: &V, other: &V) -> f64
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	let (x_dist, y_dist) = scaled_distances(map, vertex, other);
+	x_dist.max(y_dist)
+}
+
+/// Same as [`Vertex2D::manhattan_distance`], but along the axes of the graph,
+/// stretched by its [`scale`](Graph2D::scale).
+pub fn manhattan<G, V>(map: &G, vertex: &V, other: &V) -> f64
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	let (x_dist, y_dist) = scaled_distances(map, vertex, other);
+	x_dist + y_dist
+}
+
+/// Same as the [octile](Admissible::octile) heuristic, but along the axes of
+/// the graph, stretched by its [`scale`](Graph2D::scale).
+///
+/// Each diagonal step is charged the length of a stretched diagonal, and the
+/// remaining axial steps the scale of their own axis.
+pub fn octile<G, V>(map: &G, vertex: &V, other: &V) -> f64
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	let (x, y) = vertex.coords();
+	let (other_x, other_y) = other.coords();
+	let (x_dist, y_dist) = ((other_x - x).abs(), (other_y - y).abs());
+	let (x_scale, y_scale) = map.scale();
+	let diagonal = x_dist.min(y_dist);
+	let axial = (x_dist - diagonal).mul_add(x_scale, (y_dist - diagonal) * y_scale);
+	diagonal.mul_add(x_scale.hypot(y_scale), axial)
+}
+
+/// A way in which the heuristic of a graph overestimates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Violation<'m, V> {
+	/// The estimate drops by more than the cost of moving from `vertex` to
+	/// `neighbor`, by `excess`, on the way to `goal`.
+	Inconsistent {
+		vertex: &'m V,
+		neighbor: &'m V,
+		goal: &'m V,
+		excess: f64,
+	},
+	/// The goal is not estimated to be free to reach from itself.
+	NonZeroAtGoal { goal: &'m V, estimate: f64 },
+}
+
+/// Checks that the heuristic of a graph is consistent around some samples,
+/// e.g., in the test suite of a custom graph, and returns every way it isn't.
+///
+/// For every `(vertex, goal)` pair, the heuristic must estimate the goal to be
+/// free to reach from itself, and may not drop by more than the cost of any
+/// edge out of the vertex. Heuristics that are consistent everywhere are
+/// admissible, but a few samples can only ever catch them not being so.
+pub fn heuristic_violations<'m, G, V>(
+	map: &'m G,
+	samples: &[(&'m V, &'m V)],
+) -> Vec<Violation<'m, V>>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	let mut violations = Vec::new();
+	for &(vertex, goal) in samples {
+		let estimate = map.heuristic(goal, goal);
+		if estimate.abs() > COST_TOLERANCE {
+			violations.push(Violation::NonZeroAtGoal { goal, estimate });
+		}
+		let h = map.heuristic(vertex, goal);
+		for neighbor in map.neighbors(vertex) {
+			if !map.path_is_transversable(vertex, neighbor) {
+				continue;
+			}
+			let bound = map.travel_cost(vertex, neighbor) + map.heuristic(neighbor, goal);
+			let excess = h - bound;
+			if excess > COST_TOLERANCE * bound.abs().max(1.0) {
+				violations.push(Violation::Inconsistent {
+					vertex,
+					neighbor,
+					goal,
+					excess,
+				});
+			}
+		}
+	}
+	violations
+}
+
+/// Same as [`heuristic_violations`], but panics if there are any.
+///
+/// # Panics
+///
+/// If the heuristic is not consistent around any of the samples.
+pub fn assert_heuristic_admissible<'m, G, V>(map: &'m G, samples: &[(&'m V, &'m V)])
+where
+	G: Graph2D<V>,
+	V: Vertex2D + Debug,
+{
+	let violations = heuristic_violations(map, samples);
+	assert!(
+		violations.is_empty(),
+		"the heuristic overestimates: {:?}",
+		violations
+	);
+}
@@ -0,0 +1,273 @@
+//! Hierarchical pathfinding, which splits large graphs into clusters and first
+//! searches for a way between the entrances to them, only working out how to
+//! get through each cluster along the way afterwards.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+#[cfg(not(feature = "std"))]
+use crate::compat::{vec, Vec};
+use crate::compat::{HashMap, HashSet};
+use crate::search::{cmp_coords, Search};
+use crate::{neighbors, run, Graph2D, Rules, Vertex2D};
+
+use core::cmp::Ordering;
+use core::hash::Hash;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Where a cluster lies, counted in clusters along each axis.
+type Cluster = (i64, i64);
+
+/// An edge of the abstract graph, leading to an entrance or the goal, along
+/// with its cost and the path it stands for.
+type Link<'m, V> = (&'m V, f64, Vec<&'m V>);
+
+/// Hierarchical Pathfinding A*, or HPA*, over a graph split into square
+/// clusters by the coordinates of its vertices.
+///
+/// Wherever two clusters share a stretch of border, an entrance is placed
+/// across its middle, and the paths between every two entrances of a cluster
+/// are worked out ahead of time, without leaving it. Queries then only search
+/// this much smaller graph of entrances, which is far faster on large maps,
+/// and piece the path together from the ones known.
+///
+/// Since paths have to go through the middle of a border rather than
+/// wherever would be the cheapest, they may be somewhat costlier than the
+/// ones [`a_star`](crate::a_star) finds, usually by a few percent, and more so
+/// the larger the clusters. Paths staying within a cluster are always the
+/// cheapest ones, though, and whether there is a path at all is always right
+/// as long as edges can be taken both ways.
+pub struct HpaStar<'m, G, V> {
+	map: &'m G,
+	cluster_size: f64,
+	/// The entrances of every cluster.
+	entrances: HashMap<Cluster, Vec<&'m V>>,
+	/// The edges of the abstract graph leading out of every entrance.
+	links: HashMap<&'m V, Vec<Link<'m, V>>>,
+}
+
+impl<'m, G, V> HpaStar<'m, G, V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	/// Splits the given vertices of a graph into clusters of `cluster_size` on
+	/// each side and works out the paths between their entrances.
+	///
+	/// Vertices left out are never used as entrances, but paths within a
+	/// cluster may still go through them.
+	pub fn build<I>(map: &'m G, vertices: I, cluster_size: f64) -> Self
+	where
+		I: IntoIterator<Item = &'m V>,
+	{
+		let mut hpa = Self {
+			map,
+			cluster_size,
+			entrances: HashMap::new(),
+			links: HashMap::new(),
+		};
+		// The vertices of a cluster with an edge into another one, by the
+		// pair of clusters they lie between
+		let mut borders: HashMap<(Cluster, Cluster), Vec<&'m V>> = HashMap::new();
+		for vertex in vertices {
+			let cluster = hpa.cluster(vertex);
+			let mut faced: Vec<_> = neighbors(map, vertex)
+				.into_iter()
+				.filter(|&neighbor| map.path_is_transversable(vertex, neighbor))
+				.map(|neighbor| hpa.cluster(neighbor))
+				.filter(|&other| other != cluster)
+				.collect();
+			faced.sort_unstable();
+			faced.dedup();
+			for other in faced {
+				borders.entry((cluster, other)).or_default().push(vertex);
+			}
+		}
+		let mut borders: Vec<_> = borders.into_iter().collect();
+		borders.sort_unstable_by_key(|&(clusters, _)| clusters);
+		for ((_, other), border) in borders {
+			for stretch in stretches(map, &border) {
+				hpa.cross(&stretch, other);
+			}
+		}
+		let clusters: Vec<_> = hpa.entrances.keys().copied().collect();
+		for cluster in clusters {
+			hpa.connect(cluster);
+		}
+		hpa
+	}
+
+	/// Returns a path from `start` to `goal`, if there is any, searching the
+	/// entrances between them first and then filling in the way through each
+	/// cluster.
+	pub fn query(&self, start: &'m V, goal: &'m V) -> Option<Vec<&'m V>> {
+		let (start_cluster, goal_cluster) = (self.cluster(start), self.cluster(goal));
+		let entrances = |cluster| self.entrances.get(&cluster).into_iter().flatten().copied();
+		// Neither end is necessarily an entrance, so they are linked to those
+		// of their clusters, and to each other if they share one
+		let mut exits: Vec<_> = entrances(start_cluster)
+			.filter(|&entrance| entrance != start)
+			.filter_map(|entrance| self.link(start, entrance))
+			.collect();
+		if start_cluster == goal_cluster {
+			exits.extend(self.link(start, goal));
+		}
+		let arrivals: HashMap<_, _> = entrances(goal_cluster)
+			.filter(|&entrance| entrance != goal)
+			.filter_map(|entrance| Some((entrance, self.link(entrance, goal)?)))
+			.collect();
+		let outgoing = |node: &'m V| {
+			let links = self.links.get(node).into_iter().flatten();
+			let exits = exits.iter().filter(move |_| node == start);
+			links.chain(exits).chain(arrivals.get(node))
+		};
+		let mut search = Search::new(start, goal, self.map.heuristic(start, goal));
+		while let Some(cur_node) = search.pop() {
+			if cur_node == goal {
+				// Every step of the abstract path is refined into the
+				// cheapest link taking it
+				let mut path = vec![start];
+				for step in search.path_to(goal).windows(2) {
+					let (_, _, way) = outgoing(step[0])
+						.filter(|&&(to, _, _)| to == step[1])
+						.min_by(|(_, cost, _), (_, other, _)| {
+							cost.partial_cmp(other).unwrap_or(Ordering::Equal)
+						})?;
+					path.extend(&way[1..]);
+				}
+				return Some(path);
+			}
+			for &(next, cost, _) in outgoing(cur_node) {
+				search.relax(cur_node, next, cost, self.map.heuristic(next, goal));
+			}
+		}
+		None
+	}
+
+	/// Answers a batch of queries at once, spread across threads, all of them
+	/// sharing the entrances worked out ahead of time.
+	///
+	/// Paths are returned in the order of the queries.
+	#[cfg(feature = "rayon")]
+	pub fn par_query_batch(&self, queries: &[(&'m V, &'m V)]) -> Vec<Option<Vec<&'m V>>>
+	where
+		G: Sync,
+		V: Sync,
+	{
+		let queries = queries.par_iter();
+		queries
+			.map(|&(start, goal)| self.query(start, goal))
+			.collect()
+	}
+
+	/// Returns the cluster a vertex lies in.
+	#[allow(clippy::cast_possible_truncation)]
+	fn cluster(&self, vertex: &V) -> Cluster {
+		let (x, y) = vertex.coords();
+		(
+			(x / self.cluster_size).floor() as i64,
+			(y / self.cluster_size).floor() as i64,
+		)
+	}
+
+	/// Places an entrance across the middle of a stretch of border, leading
+	/// into the other cluster through the cheapest edge out of it.
+	fn cross(&mut self, stretch: &[&'m V], other: Cluster) {
+		#[allow(clippy::cast_precision_loss)]
+		let count = stretch.len() as f64;
+		let (sum_x, sum_y) = stretch.iter().fold((0.0, 0.0), |(sum_x, sum_y), vertex| {
+			let (x, y) = vertex.coords();
+			(sum_x + x, sum_y + y)
+		});
+		let (mid_x, mid_y) = (sum_x / count, sum_y / count);
+		let off_middle = |vertex: &V| {
+			let (x, y) = vertex.coords();
+			(x - mid_x).hypot(y - mid_y)
+		};
+		let middle = stretch.iter().copied().min_by(|vertex, other| {
+			let order = off_middle(vertex).partial_cmp(&off_middle(other));
+			order
+				.unwrap_or(Ordering::Equal)
+				.then_with(|| cmp_coords(*vertex, *other))
+		});
+		let Some(middle) = middle else {
+			return;
+		};
+		let map = self.map;
+		let across = neighbors(map, middle)
+			.into_iter()
+			.filter(|&neighbor| {
+				self.cluster(neighbor) == other && map.path_is_transversable(middle, neighbor)
+			})
+			.map(|neighbor| (neighbor, map.travel_cost(middle, neighbor)))
+			.min_by(|(_, cost), (_, other)| cost.partial_cmp(other).unwrap_or(Ordering::Equal));
+		let Some((neighbor, cost)) = across else {
+			return;
+		};
+		self.add_entrance(middle);
+		self.add_entrance(neighbor);
+		let links = self.links.entry(middle).or_default();
+		links.push((neighbor, cost, vec![middle, neighbor]));
+	}
+
+	fn add_entrance(&mut self, vertex: &'m V) {
+		let entrances = self.entrances.entry(self.cluster(vertex)).or_default();
+		if !entrances.contains(&vertex) {
+			entrances.push(vertex);
+		}
+	}
+
+	/// Links every two entrances of a cluster that can be reached from one
+	/// another without leaving it.
+	fn connect(&mut self, cluster: Cluster) {
+		let entrances = self.entrances.get(&cluster).cloned().unwrap_or_default();
+		for &entrance in &entrances {
+			let links: Vec<_> = entrances
+				.iter()
+				.filter(|&&other| other != entrance)
+				.filter_map(|&other| self.link(entrance, other))
+				.collect();
+			self.links.entry(entrance).or_default().extend(links);
+		}
+	}
+
+	/// Returns the cheapest path from a vertex to another that stays within the
+	/// cluster of the first, if there is any.
+	fn link(&self, from: &'m V, to: &'m V) -> Option<Link<'m, V>> {
+		let cluster = self.cluster(from);
+		let within = |_: &V, next: &V| self.cluster(next) == cluster;
+		let rules = Rules {
+			allow: Some(&within),
+			..Rules::default()
+		};
+		let search = run(self.map, &[(from, 0.0)], to, &rules)?;
+		Some((to, search.g_score(to), search.path_to(to)))
+	}
+}
+
+/// Splits a border into stretches of vertices that neighbor one another.
+fn stretches<'m, G, V>(map: &'m G, border: &[&'m V]) -> Vec<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut left: HashSet<_> = border.iter().copied().collect();
+	let mut stretches = Vec::new();
+	for &vertex in border {
+		if !left.remove(vertex) {
+			continue;
+		}
+		let mut stretch = vec![vertex];
+		let mut next = 0;
+		while let Some(&cur) = stretch.get(next) {
+			next += 1;
+			stretch.extend(
+				neighbors(map, cur)
+					.into_iter()
+					.filter(|&neighbor| left.remove(neighbor)),
+			);
+		}
+		stretches.push(stretch);
+	}
+	stretches
+}
@@ -0,0 +1,95 @@
+//! Graphs too large to be stored, whose vertices are only built as they are
+//! come across, e.g., the states of a puzzle.
+
+use crate::compat::HashMap;
+#[cfg(not(feature = "std"))]
+use crate::compat::{vec, Vec};
+use crate::search::Search;
+use crate::Vertex2D;
+
+use core::hash::Hash;
+
+/// A graph whose vertices are built anew whenever they are reached, rather than
+/// being borrowed from it, as [`Graph2D`](crate::Graph2D) expects them to be.
+pub trait LazyGraph2D<V>
+where
+	V: Vertex2D,
+{
+	/// Returns every vertex the given one leads to, along with the exact cost
+	/// of getting there.
+	fn successors(&self, vertex: &V) -> Vec<(V, f64)>;
+
+	/// Returns the estimated cost of transversing the graph from one vertex to
+	/// a goal.
+	fn heuristic(&self, vertex: &V, goal: &V) -> f64;
+}
+
+/// A state machine, whose states are told apart by what they hold rather than
+/// by where they are, e.g., the arrangements of a sliding puzzle.
+///
+/// Every move from a state to the next one costs the same, so the cheapest
+/// path is the one with the fewest moves. For it to be found, `estimate` must
+/// never count more moves than are left to reach the goal, e.g., by counting
+/// the pieces out of place. Where states are has no bearing on the search at
+/// all, so their coordinates can be anything, e.g., `(0.0, 0.0)`.
+pub trait StateGraph<S> {
+	/// Returns every state a single move leads to from the given one.
+	fn moves(&self, state: &S) -> Vec<S>;
+
+	/// Returns how many moves are at least left from a state to the goal.
+	fn estimate(&self, state: &S, goal: &S) -> f64;
+}
+
+impl<G, S> LazyGraph2D<S> for G
+where
+	G: StateGraph<S>,
+	S: Vertex2D,
+{
+	fn successors(&self, state: &S) -> Vec<(S, f64)> {
+		self.moves(state)
+			.into_iter()
+			.map(|next| (next, 1.0))
+			.collect()
+	}
+
+	fn heuristic(&self, state: &S, goal: &S) -> f64 {
+		self.estimate(state, goal)
+	}
+}
+
+/// Same as [`a_star`](crate::a_star), but on a graph whose vertices are built
+/// as the search comes across them, returning the path as owned vertices.
+///
+/// Every vertex built is kept for as long as the search runs, once per value,
+/// so equal vertices are expected to hash the same, as they are taken for one.
+/// If no path is found between the start and finish points, `None` is
+/// returned, though on infinite graphs the search may just never end.
+pub fn a_star_lazy<G, V>(map: &G, start: V, goal: &V) -> Option<Vec<V>>
+where
+	G: LazyGraph2D<V>,
+	V: Clone + Hash + Eq + Vertex2D,
+{
+	let start_h = map.heuristic(&start, goal);
+	let mut ids = HashMap::new();
+	ids.insert(start.clone(), 0);
+	let mut vertices = vec![start];
+	let mut search = Search::with_capacity(0, 0);
+	search.open(0, None, 0.0, start_h);
+	while let Some(cur_id) = search.pop() {
+		if vertices[cur_id] == *goal {
+			let path = search.path_to(cur_id).into_iter();
+			return Some(path.map(|id| vertices[id].clone()).collect());
+		}
+		for (successor, cost) in map.successors(&vertices[cur_id]) {
+			let id = if let Some(&id) = ids.get(&successor) {
+				id
+			} else {
+				ids.insert(successor.clone(), vertices.len());
+				vertices.push(successor);
+				vertices.len() - 1
+			};
+			search.relax(cur_id, id, cost, map.heuristic(&vertices[id], goal));
+		}
+	}
+	None
+}
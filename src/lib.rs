@@ -5,11 +5,65 @@
 	clippy::nursery,
 	clippy::cargo
 )]
+// The crate is not published, so there is no metadata to speak of
+#![allow(clippy::cargo_common_metadata)]
 #![allow(dead_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::collections::{HashMap, HashSet};
-use std::hash::Hash;
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("either the `std` or the `alloc` feature must be enabled");
 
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+pub mod cache;
+#[doc(hidden)]
+pub mod compat;
+pub mod cost;
+pub mod dot;
+pub mod dynamic;
+pub mod field;
+pub mod flood;
+pub mod footprint;
+pub mod grid;
+pub mod heuristic;
+pub mod hpa;
+pub mod lazy;
+pub mod motion;
+pub mod oracle;
+pub mod path;
+pub mod portal;
+pub mod roadmap;
+mod search;
+pub mod sight;
+pub mod snapshot;
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod ticked;
+pub mod undirected;
+
+use heuristic::Admissible;
+use search::Search;
+
+#[cfg(not(feature = "std"))]
+use compat::{vec, Vec};
+// Linking the standard library in for tests brings its float methods along
+#[cfg(not(any(feature = "std", test)))]
+use compat::Float;
+use compat::{HashMap, HashSet};
+
+use core::hash::{BuildHasher, Hash};
+use core::ops::ControlFlow;
+
+/// A vertex of a graph, lying somewhere on a plane.
+///
+/// Searches tell vertices apart by their `Eq` and `Hash` implementations
+/// alone, so these must take in everything about a vertex that matters to
+/// pathfinding, e.g., whether it is a wall. Vertices comparing equal are
+/// taken to be the same one, and only the first of them reached is ever
+/// expanded. Debug builds make sure the vertices equal to each other among a
+/// vertex and its neighbors at least lie at the same coordinates.
 pub trait Vertex2D {
 	/// The coordinate pair of this vertex. In a simple square grid, it could
 	/// be expected to return a (x, y) pair.
@@ -22,7 +76,7 @@ pub trait Vertex2D {
 		let (xf, yf) = other.coords();
 		let x_dist = xf - x;
 		let y_dist = yf - y;
-		(x_dist.powf(2.0) + y_dist.powf(2.0)).sqrt()
+		x_dist.hypot(y_dist)
 	}
 
 	/// Returns the largest axial distance between two vertices, i.e.,
@@ -44,16 +98,39 @@ pub trait Vertex2D {
 		let y_dist = (yf - y).abs();
 		x_dist + y_dist
 	}
+
+	/// Returns the cost of the cheapest route between two vertices made of
+	/// axial and diagonal unit steps, each at its own cost, i.e.,
+	/// `dist = ortho · (max - min) + diagonal · min`.
+	///
+	/// A diagonal step never costs more than the two axial steps it can be
+	/// replaced with, so the estimate stays admissible however costly
+	/// diagonals are made. With diagonals costing `√2` times as much as axial
+	/// steps, this is the octile distance, and with both costing the same, it
+	/// is the chebyshev distance.
+	fn octile_distance_with(&self, other: &Self, diagonal_cost: f64, ortho_cost: f64) -> f64 {
+		let (x, y) = self.coords();
+		let (xf, yf) = other.coords();
+		let x_dist = (xf - x).abs();
+		let y_dist = (yf - y).abs();
+		let (min, max) = (x_dist.min(y_dist), x_dist.max(y_dist));
+		let diagonal_cost = diagonal_cost.min(2.0 * ortho_cost);
+		diagonal_cost.mul_add(min, ortho_cost * (max - min))
+	}
 }
 
 pub trait Graph2D<V>
 where
-	V: Vertex2D,
+	V: Vertex2D + ?Sized,
 {
 	/// Returns all neighboring nodes to a given vertex.
 	fn neighbors(&self, vertex: &V) -> Vec<&V>;
 
 	/// Determines whether it is possible to travel between vertices.
+	///
+	/// Searches only ever reach a vertex through an edge this accepts, the
+	/// goal included, so vertices that can't be stood on, e.g., walls, must
+	/// have every edge into them refused to never be reached.
 	fn path_is_transversable(&self, vertex: &V, other: &V) -> bool;
 
 	/// Returns whether or not the graph has a given vertex.
@@ -66,28 +143,248 @@ where
 	/// Returns the exact cost of transversing the graph from one vertex to
 	/// its neighbor.
 	fn travel_cost(&self, vertex: &V, other: &V) -> f64;
+
+	/// Returns the exact cost of transversing the graph from one vertex to its
+	/// neighbor, knowing the vertex it was reached from, if any, e.g., for
+	/// conveyor belts or momentum, whose costs depend on the incoming
+	/// direction. Defaults to [`travel_cost`](Graph2D::travel_cost).
+	///
+	/// Searches only keep track of the cheapest way of reaching each vertex,
+	/// so when this depends on `previous`, a costlier way in that makes the
+	/// rest of the path cheaper is not considered, and paths are not
+	/// guaranteed to be optimal anymore.
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		let _ = previous;
+		self.travel_cost(vertex, other)
+	}
+
+	/// Returns every vertex the given one is a neighbor of, i.e., that it can
+	/// be reached from. Defaults to [`neighbors`](Graph2D::neighbors), as if
+	/// the graph were undirected.
+	///
+	/// Searches that work their way back from the goal rely on this, so it
+	/// must be overridden by graphs with one-way edges.
+	fn predecessors(&self, vertex: &V) -> Vec<&V> {
+		self.neighbors(vertex)
+	}
+
+	/// Returns how long a unit along each axis of the coordinates of the
+	/// vertices is, as a `(x, y)` pair, e.g., `(1.0, 2.0)` for tiles twice as
+	/// tall as they are wide. Defaults to `(1.0, 1.0)`.
+	///
+	/// The distances of the [`heuristic`](crate::heuristic) module account for
+	/// it, so they stay admissible on stretched graphs.
+	fn scale(&self) -> (f64, f64) {
+		(1.0, 1.0)
+	}
 }
 
-struct NodeInfo<N> {
-	parent: Option<N>,
-	g_score: f64,
-	f_score: f64,
+/// Implements [`Graph2D`] for a wrapper type that [`Deref`]s to another graph,
+/// forwarding every method to it, e.g., for overlays or caches that are meant
+/// to only extend the graph they wrap.
+///
+/// The vertex type must be given after the wrapper, and generic wrappers list
+/// their parameters, bounds and `where` clause just as in an `impl` block:
+///
+/// ```
+/// # use a_star::{delegate_graph2d, Graph2D, Vertex2D};
+/// # use core::ops::Deref;
+/// # struct Point;
+/// # impl Vertex2D for Point {
+/// #     fn coords(&self) -> (f64, f64) { (0.0, 0.0) }
+/// # }
+/// struct Masked<'g, G>(&'g G);
+///
+/// impl<G> Deref for Masked<'_, G> {
+///     type Target = G;
+///     fn deref(&self) -> &G {
+///         self.0
+///     }
+/// }
+///
+/// delegate_graph2d!(impl<'g, G> Masked<'g, G> => Point where G: Send);
+/// ```
+///
+/// Wrappers that change some of the methods instead forward the others to one
+/// of their fields from within their own `impl` block, by naming the field,
+/// the vertex type and the methods to forward:
+///
+/// ```
+/// # use a_star::{delegate_graph2d, Graph2D, Vertex2D};
+/// struct Doubled<G> {
+///     graph: G,
+/// }
+///
+/// impl<G: Graph2D<V>, V: Vertex2D> Graph2D<V> for Doubled<G> {
+///     delegate_graph2d!(
+///         graph => V: neighbors, path_is_transversable, has_vertex, scale
+///     );
+///
+///     fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+///         2.0 * self.graph.heuristic(vertex, other)
+///     }
+///
+///     fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+///         2.0 * self.graph.travel_cost(vertex, other)
+///     }
+/// }
+/// ```
+///
+/// [`Deref`]: core::ops::Deref
+#[macro_export]
+macro_rules! delegate_graph2d {
+	(
+		impl<$($lifetime:lifetime),* $(,)? $($param:ident $(: $bound:path)?),*>
+		$wrapper:ty => $vertex:ty
+		$(where $($clause:tt)+)?
+	) => {
+		impl<$($lifetime,)* $($param $(: $bound)?),*> $crate::Graph2D<$vertex> for $wrapper
+		where
+			Self: ::core::ops::Deref,
+			<Self as ::core::ops::Deref>::Target: $crate::Graph2D<$vertex>,
+			$($($clause)+)?
+		{
+			fn neighbors(&self, vertex: &$vertex) -> $crate::compat::Vec<&$vertex> {
+				::core::ops::Deref::deref(self).neighbors(vertex)
+			}
+
+			fn path_is_transversable(&self, vertex: &$vertex, other: &$vertex) -> bool {
+				::core::ops::Deref::deref(self).path_is_transversable(vertex, other)
+			}
+
+			fn has_vertex(&self, vertex: &$vertex) -> bool {
+				::core::ops::Deref::deref(self).has_vertex(vertex)
+			}
+
+			fn heuristic(&self, vertex: &$vertex, other: &$vertex) -> f64 {
+				::core::ops::Deref::deref(self).heuristic(vertex, other)
+			}
+
+			fn travel_cost(&self, vertex: &$vertex, other: &$vertex) -> f64 {
+				::core::ops::Deref::deref(self).travel_cost(vertex, other)
+			}
+
+			fn travel_cost_with_context(
+				&self,
+				previous: Option<&$vertex>,
+				vertex: &$vertex,
+				other: &$vertex,
+			) -> f64 {
+				::core::ops::Deref::deref(self).travel_cost_with_context(previous, vertex, other)
+			}
+
+			fn predecessors(&self, vertex: &$vertex) -> $crate::compat::Vec<&$vertex> {
+				::core::ops::Deref::deref(self).predecessors(vertex)
+			}
+
+			fn scale(&self) -> (f64, f64) {
+				::core::ops::Deref::deref(self).scale()
+			}
+		}
+	};
+	($wrapper:ty => $vertex:ty) => {
+		$crate::delegate_graph2d!(impl<> $wrapper => $vertex);
+	};
+	($field:ident => $vertex:ty: $($method:ident),+ $(,)?) => {
+		$($crate::delegate_graph2d!(@forward $method, $field, $vertex);)+
+	};
+	(@forward neighbors, $field:ident, $vertex:ty) => {
+		fn neighbors(&self, vertex: &$vertex) -> $crate::compat::Vec<&$vertex> {
+			self.$field.neighbors(vertex)
+		}
+	};
+	(@forward path_is_transversable, $field:ident, $vertex:ty) => {
+		fn path_is_transversable(&self, vertex: &$vertex, other: &$vertex) -> bool {
+			self.$field.path_is_transversable(vertex, other)
+		}
+	};
+	(@forward has_vertex, $field:ident, $vertex:ty) => {
+		fn has_vertex(&self, vertex: &$vertex) -> bool {
+			self.$field.has_vertex(vertex)
+		}
+	};
+	(@forward heuristic, $field:ident, $vertex:ty) => {
+		fn heuristic(&self, vertex: &$vertex, other: &$vertex) -> f64 {
+			self.$field.heuristic(vertex, other)
+		}
+	};
+	(@forward travel_cost, $field:ident, $vertex:ty) => {
+		fn travel_cost(&self, vertex: &$vertex, other: &$vertex) -> f64 {
+			self.$field.travel_cost(vertex, other)
+		}
+	};
+	(@forward travel_cost_with_context, $field:ident, $vertex:ty) => {
+		fn travel_cost_with_context(
+			&self,
+			previous: Option<&$vertex>,
+			vertex: &$vertex,
+			other: &$vertex,
+		) -> f64 {
+			self.$field.travel_cost_with_context(previous, vertex, other)
+		}
+	};
+	(@forward predecessors, $field:ident, $vertex:ty) => {
+		fn predecessors(&self, vertex: &$vertex) -> $crate::compat::Vec<&$vertex> {
+			self.$field.predecessors(vertex)
+		}
+	};
+	(@forward scale, $field:ident, $vertex:ty) => {
+		fn scale(&self) -> (f64, f64) {
+			self.$field.scale()
+		}
+	};
 }
 
-impl<N> Default for NodeInfo<N> {
-	fn default() -> Self {
-		const INF: f64 = std::f64::INFINITY;
-		Self {
-			parent: None,
-			g_score: INF,
-			f_score: INF,
+/// Returns the neighbors of a vertex other than itself, making sure, in debug
+/// builds, that they all belong to the graph, and that none is mistaken for
+/// another.
+///
+/// A `neighbors` implementation that hands out vertices the graph doesn't own,
+/// e.g., freshly built copies of its own, makes for very confusing searches,
+/// so this catches it right where it happens. Likewise, vertices that compare
+/// equal while lying elsewhere give away an `Eq` implementation that leaves
+/// something that matters out, under which two of them would be taken for one.
+/// A vertex neighboring itself, at its very coordinates, on the other hand, is
+/// harmless to drop, and keeping it would have searches relax the edge onto
+/// itself over and over were it to cost less than nothing.
+#[allow(clippy::float_cmp)]
+pub(crate) fn neighbors<'m, G, V>(map: &'m G, vertex: &'m V) -> Vec<&'m V>
+where
+	G: Graph2D<V>,
+	V: PartialEq + Vertex2D + ?Sized,
+{
+	let mut neighbors = map.neighbors(vertex);
+	neighbors.retain(|&neighbor| neighbor != vertex || neighbor.coords() != vertex.coords());
+	if cfg!(debug_assertions) {
+		for (index, &neighbor) in neighbors.iter().enumerate() {
+			assert!(
+				map.has_vertex(neighbor),
+				"the neighbor at {:?} of the vertex at {:?} is not part of the graph",
+				neighbor.coords(),
+				vertex.coords(),
+			);
+			let seen = neighbors[..index]
+				.iter()
+				.copied()
+				.chain(core::iter::once(vertex));
+			for other in seen.filter(|&other| other == neighbor) {
+				assert!(
+					other.coords() == neighbor.coords(),
+					"the vertices at {:?} and {:?} are equal, so they can't be told apart",
+					other.coords(),
+					neighbor.coords(),
+				);
+			}
 		}
 	}
+	neighbors
 }
 
 /// The A* algorithm calculates a path between two points on a graph by picking
 /// the points in it that are connected by the lowest costs, `f`, to reach the
-/// final goal. This score is defined as the sum of the cost of moving between
+/// final goal.
+///
+/// This score is defined as the sum of the cost of moving between
 /// vertices, `g`, and the estimated cost of reaching the goal from any
 /// vertex, `h` - which is called a heuristic function -; i.e. `f = g + h`.
 ///
@@ -103,95 +400,1249 @@ where
 	G: Graph2D<V>,
 	V: Hash + Eq + Vertex2D,
 {
-	// Estimate how many nodes need to be visited between start and goal
-	// to lower the number of allocations required
-	let (start_x, start_y) = start.coords();
+	let search = run(map, &[(start, 0.0)], goal, &Rules::default())?;
+	Some(search.path_to(goal))
+}
+
+/// Same as [`a_star`], but the open list is kept in a binary heap, rather than
+/// scanned through for the most promising node on every expansion.
+///
+/// Nodes come off it in the very same order, so the path is exactly the same,
+/// but each of them costs logarithmic time rather than linear in how large
+/// the frontier is, which makes up for the upkeep of the heap on searches
+/// that go through a lot of nodes, e.g., across wide open fields.
+pub fn a_star_heap<'m, G, V>(map: &'m G, start: &'m V, goal: &'m V) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let rules = Rules {
+		heap: true,
+		..Rules::default()
+	};
+	let search = run(map, &[(start, 0.0)], goal, &rules)?;
+	Some(search.path_to(goal))
+}
+
+/// Same as [`a_star`], but the path goes from the goal back to the start, the
+/// order in which it is naturally rebuilt, so there's no need to reverse it.
+pub fn a_star_reversed<'m, G, V>(map: &'m G, start: &'m V, goal: &'m V) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let search = run(map, &[(start, 0.0)], goal, &Rules::default())?;
+	Some(search.path_from(goal))
+}
+
+/// Same as [`a_star`], but also returns snapshots of the open list taken every
+/// `every_n` expansions, e.g., to animate the frontier as it grows.
+///
+/// Each snapshot is taken right before a vertex is expanded, which is not part
+/// of it anymore, and only holds as many vertices as the open list did then.
+/// With an `every_n` of zero, no snapshots are taken.
+pub fn a_star_with_frontier_snapshots<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	every_n: usize,
+) -> Option<(Vec<&'m V>, Vec<Vec<&'m V>>)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut snapshots = Vec::new();
+	let mut expanded = 0;
+	let mut observe = |_, search: &Search<&'m V>| {
+		expanded += 1;
+		if every_n != 0 && expanded % every_n == 0 {
+			snapshots.push(search.open_nodes().collect());
+		}
+		ControlFlow::Continue(())
+	};
+	let search = run_observed(map, &[(start, 0.0)], goal, &Rules::default(), &mut observe)?;
+	Some((search.path_to(goal), snapshots))
+}
+
+/// Same as [`a_star`], but also returns every vertex the search expanded, in
+/// the order it did, e.g., to animate the search step by step.
+///
+/// The goal is never expanded, since the search stops as soon as it gets to
+/// it, so the last vertex expanded is usually, but not always, the one the
+/// path reaches the goal from.
+pub fn a_star_expansion_order<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+) -> Option<(Vec<&'m V>, Vec<&'m V>)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut order = Vec::new();
+	let mut observe = |node, _: &Search<&'m V>| {
+		order.push(node);
+		ControlFlow::Continue(())
+	};
+	let search = run_observed(map, &[(start, 0.0)], goal, &Rules::default(), &mut observe)?;
+	Some((search.path_to(goal), order))
+}
+
+/// Same as [`a_star`], but also returns the search tree, which maps every
+/// vertex the search came across to the vertex it was reached from, e.g., to
+/// tell how a search went about.
+///
+/// The start is the root of the tree, so it isn't mapped to anything, and
+/// each vertex is mapped to the neighbor it is the cheapest to reach through,
+/// as far as the search could tell when it stopped.
+pub fn a_star_with_tree<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+) -> Option<(Vec<&'m V>, HashMap<&'m V, &'m V>)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let search = run(map, &[(start, 0.0)], goal, &Rules::default())?;
+	Some((search.path_to(goal), search.parents().collect()))
+}
+
+/// Same as [`a_star`], but the search starts from every candidate `init` gives
+/// an initial cost to, e.g., to head out from the nearest of many places.
+///
+/// The path starts at whichever candidate leads to the goal the cheapest,
+/// its initial cost included. [`a_star`] is the same as only giving the start
+/// an initial cost of zero.
+pub fn a_star_seeded<'m, G, V, F>(
+	map: &'m G,
+	candidates: &[&'m V],
+	goal: &'m V,
+	init: F,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	F: Fn(&V) -> Option<f64>,
+{
+	let seeds: Vec<_> = candidates
+		.iter()
+		.filter_map(|&node| init(node).map(|g_score| (node, g_score)))
+		.collect();
+	let search = run(map, &seeds, goal, &Rules::default())?;
+	Some(search.path_to(goal))
+}
+
+/// Same as [`a_star`], but rejects any path that takes more than `max_hops`
+/// steps between the start and the goal, regardless of its cost, e.g., a spell
+/// with a limited range.
+///
+/// Each vertex is searched once for every amount of hops it can be reached
+/// in, so a vertex reached through a long but cheap route is still expanded
+/// through a shorter, costlier one, and the returned path is the cheapest one
+/// within the hop cap. If the goal cannot be reached within `max_hops`, `None`
+/// is returned.
+pub fn a_star_max_hops<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	max_hops: usize,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut search = Search::with_capacity(0, 0);
+	search.open((start, 0), None, 0.0, map.heuristic(start, goal));
+	while let Some(state) = search.pop() {
+		let (cur_node, hops) = state;
+		if cur_node == goal {
+			let path = search.path_to(state);
+			return Some(path.into_iter().map(|(node, _)| node).collect());
+		}
+		if hops >= max_hops {
+			continue;
+		}
+		let previous = search.info(state).parent.map(|(node, _)| node);
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let cost = map.travel_cost_with_context(previous, cur_node, neighbor);
+			let h = map.heuristic(neighbor, goal);
+			search.relax(state, (neighbor, hops + 1), cost, h);
+		}
+	}
+	None
+}
+
+/// Same as [`a_star`], but each node of the path is paired with the cost of
+/// reaching it from the start, e.g., to show the time it takes to reach each
+/// waypoint.
+///
+/// The start is annotated with `0.0`, and the goal with the total cost of the
+/// path.
+pub fn a_star_annotated<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+) -> Option<Vec<(&'m V, f64)>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let search = run(map, &[(start, 0.0)], goal, &Rules::default())?;
+	let path = search.path_to(goal);
+	Some(
+		path.into_iter()
+			.map(|node| (node, search.g_score(node)))
+			.collect(),
+	)
+}
+
+/// Decides whether an edge between two vertices may be taken.
+type EdgeFilter<'r, V> = &'r dyn Fn(&V, &V) -> bool;
+
+/// Estimates the cost of going from a vertex to another.
+type Estimate<'r, V> = &'r dyn Fn(&V, &V) -> f64;
+
+/// Is told about an edge relaxed between two vertices, the g score it leads
+/// to, and whether that improved on the one known so far.
+type Watch<'r, V> = &'r dyn Fn(&V, &V, f64, bool);
+
+/// Tweaks to how the search is run, shared by its many variants.
+struct Rules<'r, V: ?Sized> {
+	/// Added to the f score of every node, so the search is nudged towards or
+	/// away from some of them.
+	bias: Option<&'r dyn Fn(&V) -> f64>,
+	/// Whether an edge may be taken at all.
+	allow: Option<EdgeFilter<'r, V>>,
+	/// Replaces the heuristic of the graph.
+	heuristic: Option<Estimate<'r, V>>,
+	/// Added to the cost of every move, times how sharply it heads away from
+	/// the goal.
+	attraction: f64,
+	/// Added to the cost of every move, from the vertex it leaves to the one
+	/// it enters.
+	penalty: Option<Estimate<'r, V>>,
+	/// Told about every edge relaxed, along with the g score it leads to and
+	/// whether it improved on the one known so far.
+	watch: Option<Watch<'r, V>>,
+	/// Whether nodes already expanded may be queued up again.
+	reopen: bool,
+	/// Whether the open list is kept in a heap, rather than scanned through.
+	heap: bool,
+}
+
+impl<V: ?Sized> Default for Rules<'_, V> {
+	fn default() -> Self {
+		Self {
+			bias: None,
+			allow: None,
+			heuristic: None,
+			attraction: 0.0,
+			penalty: None,
+			watch: None,
+			reopen: true,
+			heap: false,
+		}
+	}
+}
+
+/// Runs the search itself from every seed, each starting at its own g score,
+/// returning its bookkeeping once the goal is reached, so the path to it can
+/// be reconstructed.
+fn run<'m, G, V>(
+	map: &'m G,
+	seeds: &[(&'m V, f64)],
+	goal: &'m V,
+	rules: &Rules<V>,
+) -> Option<Search<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D + ?Sized,
+{
+	run_observed(map, seeds, goal, rules, &mut |_, _| {
+		ControlFlow::Continue(())
+	})
+}
+
+/// Same as [`run`], but `observe` is shown every node right before it is
+/// expanded, along with the search as it stands, and may give up on it.
+fn run_observed<'m, G, V>(
+	map: &'m G,
+	seeds: &[(&'m V, f64)],
+	goal: &'m V,
+	rules: &Rules<V>,
+	observe: &mut dyn FnMut(&'m V, &Search<&'m V>) -> ControlFlow<()>,
+) -> Option<Search<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D + ?Sized,
+{
+	let bias = |node: &V| rules.bias.map_or(0.0, |bias| bias(node));
+	let allow = |node: &V, other: &V| rules.allow.is_none_or(|allow| allow(node, other));
+	let h = |node: &V| {
+		let estimate = rules
+			.heuristic
+			.map_or_else(|| map.heuristic(node, goal), |h| h(node, goal));
+		estimate + bias(node)
+	};
 	let (goal_x, goal_y) = goal.coords();
-	let dx = (goal_x - start_x).abs().max(1.0);
-	let dy = (goal_y - start_y).abs().max(1.0);
-	let area = dx * dy;
-	let perimeter = 2.0 * (dx + dy);
-	let estimated_visits = if area.is_normal() { area as usize } else { 0 };
-	let estimated_analysis = if perimeter.is_normal() {
-		perimeter as usize
-	} else {
-		0
+	let heading_away = |node: &V, other: &V| {
+		let ((x, y), (other_x, other_y)) = (node.coords(), other.coords());
+		motion::sharpness((goal_x - x, goal_y - y), (other_x - x, other_y - y))
 	};
-	let mut path = Vec::new();
-	let mut open_list = HashSet::<&V>::with_capacity(estimated_analysis);
-	open_list.insert(start);
-	let mut node_info = HashMap::<&V, NodeInfo<&V>>::with_capacity(estimated_visits);
-	// The start node has a zero cost to move to, and given f = g + h,
-	// initialize that score to the heuristics alone
-	node_info.insert(
-		start,
-		NodeInfo {
-			parent: None,
-			g_score: 0.0,
-			f_score: map.heuristic(start, goal),
-		},
-	);
+	let &(first, _) = seeds.first()?;
+	let mut search = Search::between(first, goal);
+	if rules.heap {
+		search.use_heap();
+	}
+	for &(seed, g_score) in seeds {
+		search.open(seed, None, g_score, g_score + h(seed));
+	}
 	// Exhaust all pathing possibilities
-	'list: while !open_list.is_empty() {
-		let mut list = open_list.iter();
-		let cmp_node = list.next().unwrap();
-		// Pick the node with the lowest f score for analysis
-		let mut cur_node = list.fold(*cmp_node, |acc, node| {
-			let f_acc = node_info.entry(acc).or_default().f_score;
-			let f_node = node_info.entry(node).or_default().f_score;
-			if f_acc < f_node {
-				acc
-			} else {
-				*node
-			}
-		});
-		open_list.remove(cur_node);
-		for neighbor in &map.neighbors(cur_node) {
+	while let Some(cur_node) = search.pop() {
+		// Once the goal has been reached, nothing left on the frontier can
+		// lead to it any cheaper if it doesn't score below it
+		let goal_f = search.g_score(goal) + bias(goal);
+		if cur_node == goal || search.info(cur_node).f_score >= goal_f {
+			// Unless the goal was never reached, in which case nothing left
+			// can reach it, as the heuristic has ruled out every node
+			return search.g_score(goal).is_finite().then_some(search);
+		}
+		if observe(cur_node, &search).is_break() {
+			return None;
+		}
+		let previous = search.info(cur_node).parent;
+		let mut dead_end = true;
+		for neighbor in neighbors(map, cur_node) {
 			// Check its neighbors for walkability and how good of a pathing
 			// choice it is
-			if !map.path_is_transversable(cur_node, *neighbor) {
+			if !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			dead_end = false;
+			if !allow(cur_node, neighbor) {
+				continue;
+			}
+			if !rules.reopen && search.is_closed(neighbor) {
+				continue;
+			}
+			let mut cost = map.travel_cost_with_context(previous, cur_node, neighbor);
+			if rules.attraction != 0.0 {
+				cost = rules
+					.attraction
+					.mul_add(heading_away(cur_node, neighbor), cost);
+			}
+			if let Some(penalty) = rules.penalty {
+				cost += penalty(cur_node, neighbor);
+			}
+			let improved = search.relax(cur_node, neighbor, cost, h(neighbor));
+			if let Some(watch) = rules.watch {
+				watch(
+					cur_node,
+					neighbor,
+					search.g_score(cur_node) + cost,
+					improved,
+				);
+			}
+		}
+		if dead_end {
+			warn_dead_end(cur_node);
+		}
+	}
+	None
+}
+
+/// Warns, in debug builds, that a vertex expanded had no edge out of it that
+/// could be taken.
+///
+/// Nothing can be done about it but to move on, though it is worth knowing
+/// about when looking for what is wrong with a graph. Without the standard
+/// library, there is nowhere to warn to.
+#[cfg(all(feature = "std", debug_assertions))]
+fn warn_dead_end<V: Vertex2D + ?Sized>(vertex: &V) {
+	eprintln!(
+		"warning: the vertex at {:?} is a dead end, with no edge out of it",
+		vertex.coords()
+	);
+}
+
+#[cfg(not(all(feature = "std", debug_assertions)))]
+const fn warn_dead_end<V: Vertex2D + ?Sized>(_: &V) {}
+
+/// Relative tolerance under which two path costs are considered equal.
+const COST_TOLERANCE: f64 = 1e-9;
+
+/// Whether two costs are equal, give or take floating point errors.
+#[allow(clippy::float_cmp)]
+fn costs_match(cost: f64, other: f64) -> bool {
+	if cost.is_infinite() || other.is_infinite() {
+		return cost == other;
+	}
+	(cost - other).abs() <= COST_TOLERANCE * cost.abs().max(other.abs()).max(1.0)
+}
+
+/// Returns every path between the start and the goal that shares the minimum
+/// cost, instead of a single one of them as [`a_star`] does.
+///
+/// The minimum costs are calculated exhaustively, i.e., the heuristic is
+/// ignored, and every node may keep several predecessors through which it can
+/// be optimally reached. Since the amount of optimal paths can grow
+/// combinatorially, e.g., in an open grid, at most `max_paths` are enumerated.
+/// If the goal is unreachable, the returned list is empty.
+pub fn all_optimal_paths<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	max_paths: usize,
+) -> Vec<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut search = Search::new(start, goal, 0.0);
+	let mut predecessors = HashMap::<&V, Vec<&V>>::new();
+	let mut goal_g = f64::INFINITY;
+	while let Some(cur_node) = search.pop() {
+		let cur_g = search.g_score(cur_node);
+		if cur_g > goal_g && !costs_match(cur_g, goal_g) {
+			// Nothing left in the open list can be part of an optimal path
+			break;
+		}
+		if cur_node == goal {
+			goal_g = cur_g;
+			continue;
+		}
+		let previous = search.info(cur_node).parent;
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let new_g = cur_g + map.travel_cost_with_context(previous, cur_node, neighbor);
+			let old_g = search.g_score(neighbor);
+			if costs_match(new_g, old_g) {
+				predecessors.entry(neighbor).or_default().push(cur_node);
+			} else if new_g < old_g {
+				search.open(neighbor, Some(cur_node), new_g, new_g);
+				predecessors.insert(neighbor, vec![cur_node]);
+			}
+		}
+	}
+	let mut paths = Vec::new();
+	if goal_g.is_infinite() {
+		return paths;
+	}
+	// Walk the predecessors back from the goal, one partial path at a time
+	let mut partial_paths = vec![vec![goal]];
+	while let Some(partial) = partial_paths.pop() {
+		if paths.len() >= max_paths {
+			break;
+		}
+		let last = partial[partial.len() - 1];
+		if last == start {
+			let mut path = partial;
+			path.reverse();
+			paths.push(path);
+			continue;
+		}
+		for predecessor in predecessors.get(last).into_iter().flatten() {
+			if partial.contains(predecessor) {
+				// Free edges can lead back onto the path, but never to a better one
+				continue;
+			}
+			let mut next = partial.clone();
+			next.push(predecessor);
+			partial_paths.push(next);
+		}
+	}
+	paths
+}
+
+/// Finds the path between the start and the goal whose narrowest edge, as
+/// given by `width`, is as wide as possible.
+///
+/// Rather than the cheapest path, this is the corridor that lets the most
+/// through or keeps the farthest from danger. This is also known as the
+/// bottleneck or max-min path problem, and only the narrowest edge of each
+/// path matters: among paths with the same bottleneck, any may be returned
+/// regardless of its length or cost.
+/// If no path is found between the start and finish points, `None` is returned.
+pub fn widest_path<'m, G, V, W>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	width: W,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	W: Fn(&V, &V) -> f64,
+{
+	// The scores hold the negated bottleneck width of reaching each node, so
+	// the widest paths are the ones expanded first; the start itself imposes
+	// no limit
+	let mut search = Search::between(start, goal);
+	search.open(start, None, f64::NEG_INFINITY, f64::NEG_INFINITY);
+	while let Some(cur_node) = search.pop() {
+		if cur_node == goal {
+			return Some(search.path_to(goal));
+		}
+		let cur_score = search.g_score(cur_node);
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let new_score = cur_score.max(-width(cur_node, neighbor));
+			if new_score < search.g_score(neighbor) {
+				search.open(neighbor, Some(cur_node), new_score, new_score);
+			}
+		}
+	}
+	None
+}
+
+/// Finds the path between the start and the goal whose costliest single move,
+/// as given by [`Graph2D::travel_cost`], is as cheap as possible.
+///
+/// Rather than the cheapest path overall, this is the one whose hardest move
+/// is the easiest, e.g., for a robot limited by the steepest climb it can
+/// take. This is the minimax counterpart of [`widest_path`], and likewise,
+/// only the costliest move of each path matters: among paths sharing it, any
+/// may be returned regardless of its total cost.
+/// If no path is found between the start and finish points, `None` is returned.
+pub fn minimax_step_path<'m, G, V>(map: &'m G, start: &'m V, goal: &'m V) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	// The scores hold the costliest move of reaching each node, the start
+	// itself taking none at all
+	let mut search = Search::between(start, goal);
+	search.open(start, None, f64::NEG_INFINITY, f64::NEG_INFINITY);
+	while let Some(cur_node) = search.pop() {
+		if cur_node == goal {
+			return Some(search.path_to(goal));
+		}
+		let cur_score = search.g_score(cur_node);
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor) {
 				continue;
 			}
-			if **neighbor == *goal {
-				// We're done! Reconstruct the path
-				path.push(cur_node);
-				while node_info.contains_key(cur_node) {
-					if let Some(parent) = node_info[cur_node].parent {
-						cur_node = parent;
-						path.push(cur_node);
-					} else {
-						// We reached the starting node
-						break;
-					}
-				}
-				// Since the path is built from last to finish, we must reverse
-				// it before pushing the goal and returning it.
-				path.reverse();
-				path.push(goal);
-				break 'list;
-			}
-			let new_g = node_info.entry(cur_node).or_default().g_score
-				+ map.travel_cost(cur_node, neighbor);
-			let neighbor_info = node_info.entry(neighbor).or_default();
-			if neighbor_info.g_score > new_g {
-				let new_f = new_g + map.heuristic(neighbor, goal);
-				neighbor_info.f_score = new_f;
-				neighbor_info.g_score = new_g;
-				neighbor_info.parent = Some(cur_node);
-				open_list.insert(neighbor);
-			}
-		}
-	}
-	if path.is_empty() {
+			let new_score = cur_score.max(map.travel_cost(cur_node, neighbor));
+			if new_score < search.g_score(neighbor) {
+				search.open(neighbor, Some(cur_node), new_score, new_score);
+			}
+		}
+	}
+	None
+}
+
+/// Same as [`a_star`], but in a world that changes over time, in which
+/// `is_blocked` tells whether a vertex is occupied at a given tick, e.g., by a
+/// moving obstacle or another agent.
+///
+/// The search starts at tick `0` and each step, whether a move to a neighbor
+/// or staying in place, takes one tick. Waiting costs `wait_cost` per tick,
+/// and passing `None` forbids it altogether, so the agent must keep moving.
+/// Only vertices are reserved, so two agents swapping places is not detected.
+///
+/// The returned path holds where the agent is at each tick, so waiting shows
+/// up as a repeated vertex. If the goal cannot be reached within `horizon`
+/// ticks, `None` is returned.
+pub fn a_star_time_expanded<'m, G, V, B>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	is_blocked: B,
+	horizon: usize,
+	wait_cost: Option<f64>,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	B: Fn(&V, usize) -> bool,
+{
+	if is_blocked(start, 0) {
+		return None;
+	}
+	let mut search = Search::with_capacity(0, 0);
+	search.open((start, 0), None, 0.0, map.heuristic(start, goal));
+	while let Some(state) = search.pop() {
+		let (cur_node, tick) = state;
+		if cur_node == goal {
+			let path = search.path_to(state);
+			return Some(path.into_iter().map(|(node, _)| node).collect());
+		}
+		if tick >= horizon {
+			continue;
+		}
+		let next_tick = tick + 1;
+		if let Some(cost) = wait_cost {
+			if !is_blocked(cur_node, next_tick) {
+				let h = map.heuristic(cur_node, goal);
+				search.relax(state, (cur_node, next_tick), cost, h);
+			}
+		}
+		let previous = search.info(state).parent.map(|(node, _)| node);
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor) || is_blocked(neighbor, next_tick) {
+				continue;
+			}
+			let cost = map.travel_cost_with_context(previous, cur_node, neighbor);
+			let h = map.heuristic(neighbor, goal);
+			search.relax(state, (neighbor, next_tick), cost, h);
+		}
+	}
+	None
+}
+
+/// Returns a path for each unit of a group, from each start to the goal paired
+/// with it, keeping the units out of each other's way.
+///
+/// Paths are found the same way [`a_star_time_expanded`] does, holding where
+/// each unit is at every tick, so a group, e.g., a formation, moves without
+/// stacking up.
+///
+/// Units are planned one after the other, in order, each reserving where it
+/// is at every tick of its path, then its goal for good once there, and later
+/// units steering clear of those reservations, waiting for a tick at the cost
+/// of a unit of distance if they must. Units only keep out of the way of the
+/// ones planned before them, though, and swapping places goes unnoticed. A
+/// unit that can't avoid the others in time, or can't reach its goal at all,
+/// is given no path.
+///
+/// # Panics
+///
+/// If there aren't as many goals as there are starts.
+pub fn group_paths<'m, G, V>(
+	map: &'m G,
+	starts: &[&'m V],
+	goals: &[&'m V],
+) -> Vec<Option<Vec<&'m V>>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	assert_eq!(starts.len(), goals.len(), "every start needs a goal of its own");
+	let mut reserved: HashMap<&'m V, Vec<usize>> = HashMap::new();
+	let mut parked: HashMap<&'m V, usize> = HashMap::new();
+	let mut elapsed = 0;
+	let mut paths = Vec::with_capacity(starts.len());
+	for (&start, &goal) in starts.iter().zip(goals) {
+		let Some(shortest) = a_star(map, start, goal) else {
+			paths.push(None);
+			continue;
+		};
+		let is_blocked = |node: &V, tick| {
+			reserved
+				.get(node)
+				.is_some_and(|ticks| ticks.contains(&tick))
+				|| parked.get(node).is_some_and(|&since| tick >= since)
+		};
+		// A unit never has to wait longer than it takes every unit before it
+		// to get out of the way
+		let horizon = shortest.len() + elapsed;
+		let Some(path) = a_star_time_expanded(map, start, goal, is_blocked, horizon, Some(1.0))
+		else {
+			paths.push(None);
+			continue;
+		};
+		for (tick, &node) in path.iter().enumerate() {
+			reserved.entry(node).or_default().push(tick);
+		}
+		parked.insert(goal, path.len() - 1);
+		elapsed += path.len();
+		paths.push(Some(path));
+	}
+	paths
+}
+
+/// How far from the corridor given to [`a_star_guided`] its bonus fades out.
+const CORRIDOR_RADIUS: f64 = 2.0;
+
+/// Same as [`a_star`], but `forbidden` tells whether a move from `vertex` to
+/// `next` may not follow the move from `previous` to `vertex`, e.g., to ban
+/// U-turns or left turns at junctions.
+///
+/// Since whether a move is allowed depends on how its vertex was reached,
+/// the search keeps track of every way into each vertex, rather than just the
+/// cheapest one. This also makes costs depending on the incoming direction,
+/// through [`Graph2D::travel_cost_with_context`], exact. The search's very
+/// first move is never forbidden.
+pub fn a_star_with_turn_restrictions<'m, G, V, R>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	forbidden: R,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	R: Fn(&V, &V, &V) -> bool,
+{
+	let mut search = Search::with_capacity(0, 0);
+	search.open((None, start), None, 0.0, map.heuristic(start, goal));
+	while let Some(state) = search.pop() {
+		let (previous, cur_node) = state;
+		if cur_node == goal {
+			let path = search.path_to(state);
+			return Some(path.into_iter().map(|(_, node)| node).collect());
+		}
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor)
+				|| previous.is_some_and(|previous| forbidden(previous, cur_node, neighbor))
+			{
+				continue;
+			}
+			let cost = map.travel_cost_with_context(previous, cur_node, neighbor);
+			let h = map.heuristic(neighbor, goal);
+			search.relax(state, (Some(cur_node), neighbor), cost, h);
+		}
+	}
+	None
+}
+
+/// Same as [`a_star`], but biased towards following a `corridor`, e.g., a
+/// coarse route found beforehand on a simplified version of the graph.
+///
+/// Nodes on the corridor have their f score lowered by `bonus`, which fades
+/// out linearly for nodes farther away from it, up to a euclidean distance of
+/// two units; the path is still free to deviate from it. Since the bonus
+/// makes the heuristic underestimate some nodes and not others, it is no
+/// longer admissible, and the returned path may not be the cheapest one.
+pub fn a_star_guided<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	corridor: &[&V],
+	bonus: f64,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let bias = |node: &V| {
+		let distance = corridor
+			.iter()
+			.map(|waypoint| node.euclidean_distance(waypoint))
+			.fold(f64::INFINITY, f64::min);
+		-bonus * (1.0 - distance / CORRIDOR_RADIUS).max(0.0)
+	};
+	let rules = Rules {
+		bias: Some(&bias),
+		..Rules::default()
+	};
+	let search = run(map, &[(start, 0.0)], goal, &rules)?;
+	Some(search.path_to(goal))
+}
+
+/// Same as [`a_star`], but the heuristic is inflated by `epsilon`, i.e.,
+/// `f = g + ε·h`, which makes the search greedier and usually much faster.
+///
+/// As long as the heuristic is admissible, the returned path costs at most
+/// `epsilon` times as much as the cheapest one, so `epsilon` is expected to be
+/// at least `1.0`, at which point this is just A*.
+pub fn weighted_a_star<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	epsilon: f64,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	weighted_a_star_with_gap(map, start, goal, epsilon).map(|(path, _)| path)
+}
+
+/// Same as [`weighted_a_star`], but also returns how suboptimal the path may
+/// be, as an upper bound on the ratio between its cost and the cheapest one.
+///
+/// The bound is never above `epsilon`, but it is often tighter: when the goal
+/// is reached, every cheaper path would have to go through a node still in the
+/// open list, so the lowest unweighted `g + h` among them bounds the cost of
+/// the cheapest path from below. This relies on the heuristic being
+/// admissible.
+pub fn weighted_a_star_with_gap<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	epsilon: f64,
+) -> Option<(Vec<&'m V>, f64)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut search = Search::new(start, goal, epsilon * map.heuristic(start, goal));
+	while let Some(cur_node) = search.pop() {
+		let cur_g = search.g_score(cur_node);
+		if cur_node == goal {
+			let lower_bound = search
+				.open_nodes()
+				.map(|node| search.g_score(node) + map.heuristic(node, goal))
+				.fold(cur_g, f64::min);
+			let gap = if lower_bound > 0.0 {
+				(cur_g / lower_bound).max(1.0).min(epsilon.max(1.0))
+			} else {
+				1.0
+			};
+			return Some((search.path_to(goal), gap));
+		}
+		let previous = search.info(cur_node).parent;
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let cost = map.travel_cost_with_context(previous, cur_node, neighbor);
+			search.relax(
+				cur_node,
+				neighbor,
+				cost,
+				epsilon * map.heuristic(neighbor, goal),
+			);
+		}
+	}
+	None
+}
+
+/// Same as [`a_star`], for graphs whose vertices are all stored in a single
+/// slice, with the path given as indices into it, e.g., for compact storage or
+/// uploading it elsewhere.
+///
+/// The graph is expected to hand out references into `nodes` only.
+///
+/// # Panics
+///
+/// Panics if either index is out of bounds, or if a vertex of the path does
+/// not live in `nodes`.
+pub fn a_star_indices<G, V>(
+	map: &G,
+	nodes: &[V],
+	start_idx: usize,
+	goal_idx: usize,
+) -> Option<Vec<usize>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let path = a_star(map, &nodes[start_idx], &nodes[goal_idx])?;
+	let indices = path
+		.into_iter()
+		.map(|node| index_in(nodes, node).expect("the path left the given nodes"))
+		.collect();
+	Some(indices)
+}
+
+/// Returns the position of a reference within a slice, as long as it points
+/// into it.
+fn index_in<V>(nodes: &[V], node: &V) -> Option<usize> {
+	let size = core::mem::size_of::<V>();
+	if size == 0 {
+		// Every reference to a zero sized type looks the same
+		return nodes.iter().position(|other| core::ptr::eq(other, node));
+	}
+	let offset = (core::ptr::from_ref(node) as usize).checked_sub(nodes.as_ptr() as usize)?;
+	let index = offset / size;
+	if offset % size == 0 && index < nodes.len() {
+		Some(index)
+	} else {
 		None
+	}
+}
+
+/// Options for [`a_star_with_options`], all of which default to a plain A*
+/// search.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchOptions {
+	/// Added to the cost of every move, times how sharply it heads away from
+	/// the goal, as per [`turn_sharpness`](motion::turn_sharpness), so that
+	/// detours keep pulling towards the goal instead of hugging obstacles.
+	///
+	/// Anything but `0.0` makes paths look more natural at the expense of
+	/// optimality: the path found is the cheapest one including the extra
+	/// costs, not per the graph alone.
+	pub goal_attraction: f64,
+	/// Whether a vertex already expanded may be queued up again once a
+	/// cheaper way to it turns up, which is on by default.
+	///
+	/// That never happens with a consistent heuristic, one that never drops
+	/// by more than the cost of a move, so turning this off then only saves
+	/// the checks. With a heuristic that is merely admissible, it does happen,
+	/// and turning this off trades the optimality of the path for speed.
+	pub allow_reopening: bool,
+}
+
+impl Default for SearchOptions {
+	fn default() -> Self {
+		Self {
+			goal_attraction: 0.0,
+			allow_reopening: true,
+		}
+	}
+}
+
+/// Same as [`a_star`], but tweaked by some [`SearchOptions`].
+pub fn a_star_with_options<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	options: &SearchOptions,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let rules = Rules {
+		attraction: options.goal_attraction,
+		reopen: options.allow_reopening,
+		..Rules::default()
+	};
+	let search = run(map, &[(start, 0.0)], goal, &rules)?;
+	Some(search.path_to(goal))
+}
+
+/// Same as [`a_star`], but entering any vertex in `avoid` costs an extra
+/// `penalty`, so the path goes around them unless that is even costlier.
+///
+/// Unlike with [`a_star_filtered`], the vertices to avoid remain a way to the
+/// goal as a last resort. The heuristic stays admissible, since costs only go
+/// up, but the path is the cheapest one including the penalties.
+pub fn a_star_soft_avoid<'m, G, V, S>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	avoid: &HashSet<&V, S>,
+	penalty: f64,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	S: BuildHasher,
+{
+	let penalty = |_: &V, node: &V| if avoid.contains(node) { penalty } else { 0.0 };
+	let rules = Rules {
+		penalty: Some(&penalty),
+		..Rules::default()
+	};
+	let search = run(map, &[(start, 0.0)], goal, &rules)?;
+	Some(search.path_to(goal))
+}
+
+/// Returns the path [`a_star`] finds along with a backup path, if there is
+/// any, for resilient routing.
+///
+/// The backup goes through none of the vertices the first path does, save for
+/// the start and the goal, being the cheapest path left once those are taken
+/// out. This is not necessarily the cheapest pair of disjoint paths, since a
+/// costlier first path may leave room for a much cheaper backup. If the first
+/// path is a single edge, that edge is the one taken out instead.
+pub fn disjoint_backup_path<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+) -> Option<(Vec<&'m V>, Option<Vec<&'m V>>)>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let primary = a_star(map, start, goal)?;
+	if start == goal {
+		return Some((primary, None));
+	}
+	let taken: HashSet<_> = primary[1..primary.len() - 1].iter().copied().collect();
+	let allow = |node: &V, other: &V| {
+		!taken.contains(other) && (!taken.is_empty() || node != start || other != goal)
+	};
+	let backup = a_star_filtered(map, start, goal, allow);
+	Some((primary, backup))
+}
+
+/// Returns how much costlier the cheapest path between two vertices becomes
+/// with an obstacle in the way, e.g., to weigh a wall up before building it.
+///
+/// Both graphs should be the same but for the obstacle, and share the start
+/// and the goal. If the obstacle cuts the goal off, the detour is infinite,
+/// while if there is no path even without it, it costs nothing extra.
+pub fn detour_cost<'m, G, H, V>(
+	with_obstacle: &'m G,
+	without_obstacle: &'m H,
+	start: &'m V,
+	goal: &'m V,
+) -> f64
+where
+	G: Graph2D<V>,
+	H: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let rules = Rules::default();
+	let Some(search) = run(without_obstacle, &[(start, 0.0)], goal, &rules) else {
+		return 0.0;
+	};
+	let cost = search.g_score(goal);
+	run(with_obstacle, &[(start, 0.0)], goal, &rules)
+		.map_or(f64::INFINITY, |search| search.g_score(goal) - cost)
+}
+
+/// Same as [`a_star`], but an edge between two vertices is only taken if
+/// `allow` accepts it, e.g., to mask parts of the graph out, keep the search
+/// within some bounds, or enforce movement rules.
+///
+/// Since edges are only ever taken away, the true cost of reaching the goal
+/// can only go up, so an admissible heuristic stays admissible, though it may
+/// become a looser estimate.
+pub fn a_star_filtered<'m, G, V, F>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	allow: F,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	F: Fn(&V, &V) -> bool,
+{
+	let rules = Rules {
+		allow: Some(&allow),
+		..Rules::default()
+	};
+	let search = run(map, &[(start, 0.0)], goal, &rules)?;
+	Some(search.path_to(goal))
+}
+
+/// Same as [`a_star`], but estimating with a heuristic known to be admissible
+/// instead of the one of the graph, so the path is guaranteed to be optimal
+/// as long as the heuristic suits the graph.
+pub fn a_star_admissible<'m, G, V, H>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	heuristic: &Admissible<H>,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	H: Fn(&V, &V) -> f64,
+{
+	let estimate = |node: &V, goal: &V| heuristic.estimate(node, goal);
+	let rules = Rules {
+		heuristic: Some(&estimate),
+		..Rules::default()
+	};
+	let search = run(map, &[(start, 0.0)], goal, &rules)?;
+	Some(search.path_to(goal))
+}
+
+/// Same as [`a_star`], but searching from both the start and the goal at once,
+/// until both searches meet, which usually expands fewer vertices overall.
+///
+/// The search from the goal follows edges backwards, through
+/// [`predecessors`](Graph2D::predecessors), and estimates the cost of reaching
+/// each vertex from the start as `heuristic(vertex, start)`. Travel costs
+/// don't depend on how a vertex was reached, as the search from the goal
+/// can't know it.
+pub fn bidirectional_a_star<'m, G, V>(map: &'m G, start: &'m V, goal: &'m V) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut forward = Search::new(start, goal, map.heuristic(start, goal));
+	let mut backward = Search::new(goal, start, map.heuristic(goal, start));
+	// The vertex through which the cheapest path found so far goes
+	let mut best = if start == goal {
+		Some((start, 0.0))
 	} else {
-		// println!(
-		// 	"{} {} / {} {}",
-		// 	estimated_visits,
-		// 	node_info.len(),
-		// 	estimated_analysis,
-		// 	open_list.capacity()
-		// );
-		Some(path)
+		None
+	};
+	let mut forward_turn = true;
+	loop {
+		let expanded = if forward_turn {
+			expand_towards(map, &mut forward, &backward, goal, true, &mut best)
+		} else {
+			expand_towards(map, &mut backward, &forward, start, false, &mut best)
+		};
+		if !expanded {
+			break;
+		}
+		forward_turn = !forward_turn;
+	}
+	let (meeting, _) = best?;
+	let mut path = forward.path_to(meeting);
+	path.extend(backward.path_from(meeting).into_iter().skip(1));
+	Some(path)
+}
+
+/// Expands the best node of one side of a bidirectional search, heading
+/// towards `target`, and keeps track of where it meets the other side the
+/// cheapest. Returns whether there may still be a cheaper meeting point.
+fn expand_towards<'m, G, V>(
+	map: &'m G,
+	search: &mut Search<&'m V>,
+	other: &Search<&'m V>,
+	target: &'m V,
+	forward: bool,
+	best: &mut Option<(&'m V, f64)>,
+) -> bool
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let best_cost = best.map_or(f64::INFINITY, |(_, cost)| cost);
+	let Some(cur_node) = search.pop() else {
+		return false;
+	};
+	// Every path through what's left of this side is at least this costly
+	if search.info(cur_node).f_score >= best_cost {
+		return false;
+	}
+	let next_nodes = if forward {
+		neighbors(map, cur_node)
+	} else {
+		map.predecessors(cur_node)
+	};
+	for next in next_nodes {
+		let (from, to) = if forward {
+			(cur_node, next)
+		} else {
+			(next, cur_node)
+		};
+		if !map.path_is_transversable(from, to) {
+			continue;
+		}
+		let cost = map.travel_cost(from, to);
+		if search.relax(cur_node, next, cost, map.heuristic(next, target)) {
+			let through = search.g_score(next) + other.g_score(next);
+			if through < best.map_or(f64::INFINITY, |(_, cost)| cost) {
+				*best = Some((next, through));
+			}
+		}
+	}
+	true
+}
+
+/// Same as [`a_star`], but using iterative deepening, i.e., searching depth
+/// first over and over, going further each time, to use very little memory.
+///
+/// Every pass only goes down paths whose f score stays within a bound, which
+/// starts at the heuristic estimate of the start and grows to the smallest f
+/// score that was out of bounds on the previous pass. Only the current path,
+/// and the neighbors left to try along it, are kept in memory, at the expense
+/// of expanding many vertices over and over, so this is only worth it when
+/// memory is very scarce.
+pub fn ida_star<'m, G, V>(map: &'m G, start: &'m V, goal: &'m V) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut path = Vec::new();
+	let mut bound = map.heuristic(start, goal);
+	loop {
+		match deepen(map, start, bound, goal, &mut path) {
+			Deepening::Found => return Some(path),
+			Deepening::Exceeded(next_bound) if next_bound.is_finite() => bound = next_bound,
+			Deepening::Exceeded(_) => return None,
+		}
+	}
+}
+
+/// The outcome of a single depth first pass of [`ida_star`].
+enum Deepening {
+	/// The path leads to the goal.
+	Found,
+	/// The goal couldn't be reached within bounds, and this is the smallest f
+	/// score that was out of them.
+	Exceeded(f64),
+}
+
+/// Searches depth first from `start` for the goal within an f score of
+/// `bound`, leaving the way there in `path`.
+///
+/// The search keeps its own stack of the neighbors left to try at every depth
+/// rather than recursing, so that long paths can't overflow the call stack.
+fn deepen<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	bound: f64,
+	goal: &'m V,
+	path: &mut Vec<&'m V>,
+) -> Deepening
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	path.clear();
+	path.push(start);
+	// The neighbors yet to be tried from every vertex of the path that was
+	// expanded, which is all of them but the last, along with its g score
+	let mut branches: Vec<(Vec<&'m V>, f64)> = Vec::new();
+	let mut g_score = 0.0;
+	let mut next_bound = f64::INFINITY;
+	loop {
+		let cur_node = path[path.len() - 1];
+		let f_score = g_score + map.heuristic(cur_node, goal);
+		if f_score > bound {
+			next_bound = next_bound.min(f_score);
+			path.pop();
+		} else if cur_node == goal {
+			return Deepening::Found;
+		} else {
+			let mut untried = neighbors(map, cur_node);
+			// Neighbors are tried last to first otherwise
+			untried.reverse();
+			branches.push((untried, g_score));
+		}
+		// Backtrack up to the deepest vertex with a neighbor left to try
+		loop {
+			let Some((untried, branch_g)) = branches.last_mut() else {
+				return Deepening::Exceeded(next_bound);
+			};
+			let Some(neighbor) = untried.pop() else {
+				branches.pop();
+				path.pop();
+				continue;
+			};
+			let cur_node = path[path.len() - 1];
+			// Going around in circles never leads anywhere cheaper
+			if path.contains(&neighbor) || !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let previous = path.len().checked_sub(2).map(|i| path[i]);
+			g_score = *branch_g + map.travel_cost_with_context(previous, cur_node, neighbor);
+			path.push(neighbor);
+			break;
+		}
+	}
+}
+
+/// Same as [`a_star`], but falls back to [`ida_star`] if the search needs to
+/// keep track of more than `mem_limit_nodes` vertices, e.g., on huge graphs.
+///
+/// This is just as fast as [`a_star`] when memory allows, and when it doesn't,
+/// the work done so far is thrown away and the much slower, but frugal,
+/// iterative deepening search takes over.
+pub fn a_star_adaptive<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	mem_limit_nodes: usize,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut exceeded = false;
+	let mut observe = |_, search: &Search<&'m V>| {
+		exceeded = search.seen() > mem_limit_nodes;
+		if exceeded {
+			ControlFlow::Break(())
+		} else {
+			ControlFlow::Continue(())
+		}
+	};
+	match run_observed(map, &[(start, 0.0)], goal, &Rules::default(), &mut observe) {
+		Some(search) => Some(search.path_to(goal)),
+		None if exceeded => ida_star(map, start, goal),
+		None => None,
 	}
 }
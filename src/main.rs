@@ -5,11 +5,11 @@
 	clippy::nursery,
 	clippy::cargo
 )]
+// The crate is not published, so there is no metadata to speak of
+#![allow(clippy::cargo_common_metadata)]
 
-// This is an RLS issue with lib/main crates and this is the workaround
-// https://github.com/rust-lang/rls-vscode/issues/686
-mod lib;
-use lib::*;
+use a_star::grid::{grid_neighbors_8, step_is_clear};
+use a_star::{a_star, Graph2D, Vertex2D};
 
 use std::fmt::Debug;
 use std::ops::Deref;
@@ -30,6 +30,7 @@ impl Debug for Node {
 }
 
 impl Vertex2D for Node {
+	#[allow(clippy::cast_precision_loss)]
 	fn coords(&self) -> (f64, f64) {
 		(self.x as f64, self.y as f64)
 	}
@@ -83,7 +84,7 @@ impl D2Q9 {
 	}
 
 	fn get_at(&self, x: usize, y: usize) -> Option<&Node> {
-		self.get(y).map(|row| row.get(x)).flatten()
+		self.get(y).and_then(|row| row.get(x))
 	}
 }
 
@@ -92,40 +93,13 @@ impl Graph2D<Node> for D2Q9 {
 		self.get_at(node.x, node.y).is_some()
 	}
 	fn neighbors(&self, from: &Node) -> Vec<&Node> {
-		macro_rules! resolve_or_continue {
-			($axis:expr => $delta:ident) => {
-				match $axis
-					.checked_add($delta)
-					.map(|coord| coord.checked_sub(1))
-					.flatten()
-					{
-					None => continue,
-					Some(coord) => coord,
-					}
-			};
-			(@ $x:ident $y:ident) => {
-				match self.get($y).map(|row| row.get($x)).flatten() {
-					None => continue,
-					Some(node) => node,
-					}
-			};
-		}
-		let mut neighbors = Vec::with_capacity(8);
-		for dy in 0..=2 {
-			for dx in 0..=2 {
-				if dx == dy && dx == 1 {
-					continue;
-				}
-				let x = resolve_or_continue!(from.x => dx);
-				let y = resolve_or_continue!(from.y => dy);
-				let neighbor = resolve_or_continue!(@ x y);
-				neighbors.push(neighbor);
-			}
-		}
-		neighbors
+		let width = self.first().map_or(0, Vec::len);
+		grid_neighbors_8((from.x, from.y), width, self.len())
+			.into_iter()
+			.filter_map(|(x, y)| self.get_at(x, y))
+			.collect()
 	}
 
-	#[allow(clippy::float_cmp)]
 	fn path_is_transversable(&self, from: &Node, to: &Node) -> bool {
 		if from == to {
 			return true;
@@ -133,22 +107,9 @@ impl Graph2D<Node> for D2Q9 {
 		if from.is_wall || to.is_wall {
 			return false;
 		}
-		let dist_x = to.x as f64 - from.x as f64;
-		let dist_y = to.y as f64 - from.y as f64;
-		if (from.x == to.x && dist_y.abs() == 1.0) || (from.y == to.y && dist_x.abs() == 1.0) {
-			return true;
-		}
-		if dist_x.abs() == dist_y.abs() && dist_x.abs() == 1.0 {
-			return Some(false)
-				== self
-					.get_at(from.x, (from.y as f64 + dist_y) as usize)
-					.map(|node| node.is_wall)
-				|| Some(false)
-					== self
-						.get_at((from.x as f64 + dist_x) as usize, from.y)
-						.map(|node| node.is_wall);
-		}
-		false
+		step_is_clear((from.x, from.y), (to.x, to.y), |x, y| {
+			self.get_at(x, y).is_some_and(|node| !node.is_wall)
+		})
 	}
 
 	fn heuristic(&self, node: &Node, other: &Node) -> f64 {
@@ -164,11 +125,11 @@ impl D2Q9 {
 	fn path_and_show<'p>(&'p self, from: &'p Node, to: &'p Node) -> Option<Vec<&'p Node>> {
 		let path = a_star(self, from, to)?;
 		for row in self.iter() {
-			for node in row.iter() {
+			for node in row {
 				if node.x == from.x && node.y == from.y {
-					print!("S")
+					print!("S");
 				} else if node.x == to.x && node.y == to.y {
-					print!("E")
+					print!("E");
 				} else if node.is_wall {
 					print!("O");
 				} else if path.contains(&node) {
@@ -189,12 +150,12 @@ fn main() {
 	let grid = D2Q9::new(50, 20, |x, y| {
 		// (x % 10 == 3 && y > 5) || (x % 10 == 8 && y < 15)
 		// x < 48 && x / 5 == y && y < 15
-		(x == 5 && y >= 3 && y <= 5)
-			|| (x == 30 && y >= 5 && y <= 10)
-			|| (x == 35 && y <= 10 && y >= 3)
-			|| (y == 3 && x >= 5 && x <= 35)
-			|| (y == 5 && x >= 5 && x <= 30)
-			|| (y == 10 && x >= 30 && x <= 35)
+		(x == 5 && (3..=5).contains(&y))
+			|| (x == 30 && (5..=10).contains(&y))
+			|| (x == 35 && (3..=10).contains(&y))
+			|| (y == 3 && (5..=35).contains(&x))
+			|| (y == 5 && (5..=30).contains(&x))
+			|| (y == 10 && (30..=35).contains(&x))
 	});
 	grid.path_and_show(&Node::new(0, 19), &Node::new(37, 1));
 }
@@ -0,0 +1,228 @@
+//! Costs that account for how agents move, rather than just where they go.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::search::Search;
+use crate::{neighbors, Graph2D, Vertex2D};
+
+use core::hash::Hash;
+use core::ops::Deref;
+
+/// How sharp a turn has to be to count as one, so that going straight is not
+/// mistaken for turning due to rounding.
+const STRAIGHT: f64 = 1e-9;
+
+/// Returns how sharp the turn from the segment `previous`-`vertex` onto the
+/// segment `vertex`-`other` is.
+///
+/// This is `1 - cos θ`, θ being the angle between them: `0` when going
+/// straight, `1` at right angles, and `2` when turning back. Segments of no
+/// length don't turn at all.
+pub fn turn_sharpness<V: Vertex2D>(previous: &V, vertex: &V, other: &V) -> f64 {
+	let (x, y) = previous.coords();
+	let (next_x, next_y) = vertex.coords();
+	let (last_x, last_y) = other.coords();
+	sharpness((next_x - x, next_y - y), (last_x - next_x, last_y - next_y))
+}
+
+/// Returns `1 - cos θ`, θ being the angle between two directions, or `0` if
+/// either of them has no length.
+pub(crate) fn sharpness((in_x, in_y): (f64, f64), (out_x, out_y): (f64, f64)) -> f64 {
+	let lengths = in_x.hypot(in_y) * out_x.hypot(out_y);
+	if lengths == 0.0 {
+		return 0.0;
+	}
+	1.0 - in_x.mul_add(out_x, in_y * out_y) / lengths
+}
+
+/// A graph in which every move also costs how sharply it turns away from the
+/// move before it, times a weight, so that searches favor smooth paths.
+///
+/// Such paths are easier to follow for agents with limited acceleration.
+///
+/// The penalty is added through [`Graph2D::travel_cost_with_context`], so a
+/// search's very first move is never penalized. Since costs only go up, an
+/// admissible heuristic stays admissible, but the path is subject to the same
+/// caveat as any other context dependent cost: only the cheapest way into
+/// each vertex is kept, so a costlier way in setting up a smoother path out
+/// may be missed.
+pub struct TurnPenalty<G> {
+	graph: G,
+	weight: f64,
+}
+
+impl<G> TurnPenalty<G> {
+	pub const fn new(graph: G, weight: f64) -> Self {
+		Self { graph, weight }
+	}
+
+	/// Unwraps the graph.
+	pub fn into_inner(self) -> G {
+		self.graph
+	}
+}
+
+impl<G> Deref for TurnPenalty<G> {
+	type Target = G;
+
+	fn deref(&self) -> &G {
+		&self.graph
+	}
+}
+
+impl<G, V> Graph2D<V> for TurnPenalty<G>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	crate::delegate_graph2d!(
+		graph => V: neighbors, path_is_transversable, has_vertex, heuristic, travel_cost,
+		predecessors, scale
+	);
+
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		let cost = self.graph.travel_cost_with_context(previous, vertex, other);
+		let sharpness = previous.map_or(0.0, |previous| turn_sharpness(previous, vertex, other));
+		self.weight.mul_add(sharpness, cost)
+	}
+}
+
+/// A graph over which a constant drift, e.g., wind or a current, carries agents
+/// along, speeding moves with it up and slowing those against it down.
+///
+/// The drift is given as a `(x, y)` vector, as a fraction of the speed of the
+/// agents, so that a move along direction `d̂` goes `1 + d̂·drift` as fast, and
+/// costs as much less, or more. It has to be shorter than `1`, so that agents
+/// can still make headway against it. The heuristic of the graph is divided
+/// by how much faster the drift could at best make agents go, which is when
+/// heading straight downwind, so it stays admissible as long as that one is.
+///
+/// Going straight is not always the fastest way: when the drift is strong, a
+/// way across it may well beat beating against it.
+pub struct DriftGraph<'g, G> {
+	graph: &'g G,
+	drift: (f64, f64),
+}
+
+impl<'g, G> DriftGraph<'g, G> {
+	/// # Panics
+	///
+	/// If the drift is not shorter than `1`, as agents could then be brought
+	/// to a standstill, or carried backwards.
+	pub fn new(graph: &'g G, drift: (f64, f64)) -> Self {
+		assert!(
+			drift.0.hypot(drift.1) < 1.0,
+			"the drift outruns the agents: {:?}",
+			drift
+		);
+		Self { graph, drift }
+	}
+
+	/// Returns how much faster than on its own a move from `vertex` to
+	/// `other` is made to go by the drift.
+	fn speedup<V>(&self, vertex: &V, other: &V) -> f64
+	where
+		G: Graph2D<V>,
+		V: Vertex2D,
+	{
+		let ((x, y), (other_x, other_y)) = (vertex.coords(), other.coords());
+		let (scale_x, scale_y) = self.graph.scale();
+		let (dx, dy) = ((other_x - x) * scale_x, (other_y - y) * scale_y);
+		let length = dx.hypot(dy);
+		if length == 0.0 {
+			return 1.0;
+		}
+		let (drift_x, drift_y) = self.drift;
+		1.0 + dx.mul_add(drift_x, dy * drift_y) / length
+	}
+}
+
+impl<G, V> Graph2D<V> for DriftGraph<'_, G>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	crate::delegate_graph2d!(
+		graph => V: neighbors, path_is_transversable, has_vertex, predecessors, scale
+	);
+
+	fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+		let (drift_x, drift_y) = self.drift;
+		self.graph.heuristic(vertex, other) / (1.0 + drift_x.hypot(drift_y))
+	}
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		self.graph.travel_cost(vertex, other) / self.speedup(vertex, other)
+	}
+
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		self.graph.travel_cost_with_context(previous, vertex, other) / self.speedup(vertex, other)
+	}
+}
+
+/// Returns whether a move onto `other` keeps heading the same way as the move
+/// from `previous` onto `vertex`, i.e., isn't a turn.
+fn goes_straight<V: Vertex2D>(previous: &V, vertex: &V, other: &V) -> bool {
+	turn_sharpness(previous, vertex, other) <= STRAIGHT
+}
+
+/// Same as [`a_star`](crate::a_star), but every turn along the path, however
+/// sharp, also costs a fixed `turn_cost`.
+///
+/// Searches then trade length for fewer turns, e.g., for vehicles that have
+/// to slow down at each of them.
+///
+/// Whether a move is a turn depends on how its vertex was reached, so the
+/// search keeps track of every way into each vertex, which makes the path the
+/// cheapest one, turns included. The heuristic of the graph is topped up with
+/// one penalty whenever the goal isn't straight ahead, as at least one more
+/// turn is then unavoidable, or, at the start, when no neighbor leads straight
+/// towards it, so it stays admissible as long as the graph's heuristic is.
+pub fn a_star_turn_penalty<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	turn_cost: f64,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let heuristic = |previous: &V, vertex: &V| {
+		let h = map.heuristic(vertex, goal);
+		if vertex == goal || goes_straight(previous, vertex, goal) {
+			h
+		} else {
+			h + turn_cost
+		}
+	};
+	let mut search = Search::with_capacity(0, 0);
+	let leads_straight = |neighbor: &&V| *neighbor == goal || goes_straight(start, neighbor, goal);
+	let start_h = if start == goal || map.neighbors(start).iter().any(leads_straight) {
+		map.heuristic(start, goal)
+	} else {
+		map.heuristic(start, goal) + turn_cost
+	};
+	search.open((None, start), None, 0.0, start_h);
+	while let Some(state) = search.pop() {
+		let (previous, cur_node) = state;
+		if cur_node == goal {
+			let path = search.path_to(state);
+			return Some(path.into_iter().map(|(_, node)| node).collect());
+		}
+		for neighbor in neighbors(map, cur_node) {
+			if !map.path_is_transversable(cur_node, neighbor) {
+				continue;
+			}
+			let mut cost = map.travel_cost_with_context(previous, cur_node, neighbor);
+			if previous.is_some_and(|previous| !goes_straight(previous, cur_node, neighbor)) {
+				cost += turn_cost;
+			}
+			let h = heuristic(cur_node, neighbor);
+			search.relax(state, (Some(cur_node), neighbor), cost, h);
+		}
+	}
+	None
+}
@@ -0,0 +1,125 @@
+//! Distance oracles, which tell how far apart any two vertices are, or at least
+//! bound it, from work done ahead of time, so that searches can be guided by
+//! them far better than by coordinates alone.
+
+use crate::compat::HashMap;
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::field::cost_field;
+use crate::flood::reachable_within;
+use crate::{run, Graph2D, Rules, Vertex2D};
+
+use core::hash::Hash;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Answers how costly the cheapest path between two vertices is, without
+/// searching for it.
+///
+/// An oracle is used as a heuristic, so it must never answer more than the
+/// true cost, but it may answer less, leaving the search more work to do.
+pub trait DistanceOracle<V> {
+	/// Returns the cost of the cheapest path from `vertex` to `other`, or a
+	/// lower bound on it.
+	fn distance(&self, vertex: &V, other: &V) -> f64;
+}
+
+/// Same as [`a_star`](crate::a_star), but guided by a distance oracle instead
+/// of the heuristic of the graph.
+pub fn a_star_with_oracle<'m, G, V, O>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	oracle: &O,
+) -> Option<Vec<&'m V>>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+	O: DistanceOracle<V>,
+{
+	let estimate = |node: &V, goal: &V| oracle.distance(node, goal);
+	let rules = Rules {
+		heuristic: Some(&estimate),
+		..Rules::default()
+	};
+	let search = run(map, &[(start, 0.0)], goal, &rules)?;
+	Some(search.path_to(goal))
+}
+
+/// The costs of the cheapest paths between a vertex and every other one.
+type Costs<'m, V> = HashMap<&'m V, f64>;
+
+/// An oracle that knows the costs of the cheapest paths from and to a handful
+/// of landmarks, and bounds any other cost by the triangle inequality.
+///
+/// Going from a vertex to another and then on to a landmark is never cheaper
+/// than heading to the landmark right away, so the difference between the
+/// costs of reaching it from both is a lower bound on the cost of going from
+/// one to the other, and likewise for the costs of getting to each of them
+/// from the landmark. This is the ALT heuristic, for A*, landmarks and the
+/// triangle inequality.
+///
+/// Bounds are tightest for vertices lined up with a landmark, so landmarks
+/// far out on the edges of the graph work best. Building the oracle takes two
+/// floods of the whole graph per landmark, and as much memory.
+pub struct Landmarks<'m, V> {
+	/// The costs of reaching every vertex from each landmark, and of reaching
+	/// each landmark from every vertex.
+	costs: Vec<(Costs<'m, V>, Costs<'m, V>)>,
+}
+
+impl<'m, V> Landmarks<'m, V>
+where
+	V: Hash + Eq + Vertex2D,
+{
+	pub fn new<G: Graph2D<V>>(map: &'m G, landmarks: &[&'m V]) -> Self {
+		let costs = landmarks
+			.iter()
+			.map(|&landmark| {
+				(
+					reachable_within(map, landmark, f64::INFINITY),
+					cost_field(map, landmark),
+				)
+			})
+			.collect();
+		Self { costs }
+	}
+
+	/// Answers a batch of queries at once, spread across threads, each of them
+	/// searched as [`a_star_with_oracle`] would, guided by these landmarks.
+	///
+	/// Landmarks are only ever read once built, so every thread shares them.
+	/// Paths are returned in the order of the queries.
+	#[cfg(feature = "rayon")]
+	pub fn par_query_batch<G>(
+		&self,
+		map: &'m G,
+		queries: &[(&'m V, &'m V)],
+	) -> Vec<Option<Vec<&'m V>>>
+	where
+		G: Graph2D<V> + Sync,
+		V: Sync,
+	{
+		let queries = queries.par_iter();
+		queries
+			.map(|&(start, goal)| a_star_with_oracle(map, start, goal, self))
+			.collect()
+	}
+}
+
+impl<V> DistanceOracle<V> for Landmarks<'_, V>
+where
+	V: Hash + Eq + Vertex2D,
+{
+	fn distance(&self, vertex: &V, other: &V) -> f64 {
+		// A landmark that misses either vertex tells nothing about them
+		let bound = |costs: &Costs<V>, from: &V, to: &V| match (costs.get(from), costs.get(to)) {
+			(Some(from), Some(to)) => to - from,
+			_ => 0.0,
+		};
+		let bounds = self.costs.iter().map(|(from_landmark, to_landmark)| {
+			bound(from_landmark, vertex, other).max(bound(to_landmark, other, vertex))
+		});
+		bounds.fold(0.0, f64::max)
+	}
+}
@@ -0,0 +1,267 @@
+//! Utilities to make sense of paths once they are found, relying only on the
+//! coordinates of their vertices, or on what the search annotated them with.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+#[cfg(not(feature = "std"))]
+use crate::compat::{vec, Vec};
+use crate::{Graph2D, Vertex2D};
+
+/// Returns the movement between two vertices as a `(Δx, Δy)` pair.
+fn delta<V: Vertex2D>(from: &V, to: &V) -> (f64, f64) {
+	let (x, y) = from.coords();
+	let (to_x, to_y) = to.coords();
+	(to_x - x, to_y - y)
+}
+
+/// Whether two movements head in the same direction, regardless of how long
+/// they are.
+fn same_direction((x, y): (f64, f64), (other_x, other_y): (f64, f64)) -> bool {
+	let cross = x.mul_add(other_y, -y * other_x);
+	let dot = x.mul_add(other_x, y * other_y);
+	cross.abs() <= 1e-9 * x.hypot(y) * other_x.hypot(other_y) && dot > 0.0
+}
+
+/// Keeps only the corners of a path, i.e., its endpoints and the vertices at
+/// which the direction of movement changes, collapsing straight runs.
+///
+/// Steps that stay in place don't count as a change of direction.
+pub fn path_corners<'p, V: Vertex2D>(path: &[&'p V]) -> Vec<&'p V> {
+	let (Some(&first), Some(&last)) = (path.first(), path.last()) else {
+		return Vec::new();
+	};
+	let mut corners = vec![first];
+	let mut heading = None;
+	for step in path.windows(2) {
+		let movement = delta(step[0], step[1]);
+		if movement == (0.0, 0.0) {
+			continue;
+		}
+		match heading {
+			Some(heading) if !same_direction(heading, movement) => corners.push(step[0]),
+			_ => {}
+		}
+		heading = Some(movement);
+	}
+	if path.len() > 1 {
+		corners.push(last);
+	}
+	corners
+}
+
+/// Thins a path down to at most `max_points` of its vertices, those that keep
+/// it as close to its original shape as possible, e.g., to send it over.
+///
+/// This is the Ramer-Douglas-Peucker algorithm, with the tolerance lowered
+/// only as far as the budget allows: starting from the endpoints, which are
+/// kept if they fit, the vertex straying furthest from the simplified path is
+/// put back in, one at a time, until either the budget is spent or every
+/// other vertex lies right on the simplified path. A budget of a single vertex
+/// only keeps the start, and one of none keeps nothing at all.
+pub fn simplify_path<'p, V: Vertex2D>(path: &[&'p V], max_points: usize) -> Vec<&'p V> {
+	if max_points < 2 || path.len() <= 2 {
+		// Either both endpoints don't fit, or there is nothing else to keep
+		return path.iter().take(max_points).copied().collect();
+	}
+	let points: Vec<_> = path.iter().map(|vertex| vertex.coords()).collect();
+	let mut kept = vec![false; path.len()];
+	kept[0] = true;
+	kept[path.len() - 1] = true;
+	for _ in 2..max_points {
+		match furthest_stray(&points, &kept) {
+			Some((index, deviation)) if deviation > 1e-9 => kept[index] = true,
+			_ => break,
+		}
+	}
+	path.iter()
+		.zip(kept)
+		.filter(|&(_, kept)| kept)
+		.map(|(&vertex, _)| vertex)
+		.collect()
+}
+
+/// Returns the point not `kept` the furthest from the path through those that
+/// are, along with how far from it it is, if there is any such point.
+fn furthest_stray(points: &[(f64, f64)], kept: &[bool]) -> Option<(usize, f64)> {
+	let mut furthest = None::<(usize, f64)>;
+	let mut from = 0;
+	for to in (1..points.len()).filter(|&index| kept[index]) {
+		for index in from + 1..to {
+			let point = points[index];
+			let (x, y) = nearest_on_segment(points[from], points[to], point);
+			let deviation = (point.0 - x).hypot(point.1 - y);
+			if furthest.is_none_or(|(_, furthest)| deviation > furthest) {
+				furthest = Some((index, deviation));
+			}
+		}
+		from = to;
+	}
+	furthest
+}
+
+/// Returns the velocity to steer with to follow a path from `pos`, by pure
+/// pursuit: heading straight for a point `lookahead` further down the path.
+///
+/// That point is measured along the path from the point of it nearest to
+/// `pos`, so turns are anticipated by cutting them short. The velocity is at
+/// most `max_speed` long, and shrinks to land right on the end of the path
+/// when it is that close. An empty path doesn't go anywhere.
+pub fn path_follow_velocity<V: Vertex2D>(
+	path: &[&V],
+	pos: (f64, f64),
+	max_speed: f64,
+	lookahead: f64,
+) -> (f64, f64) {
+	let points: Vec<_> = path.iter().map(|vertex| vertex.coords()).collect();
+	let Some((target_x, target_y)) = lookahead_point(&points, pos, lookahead) else {
+		return (0.0, 0.0);
+	};
+	let (x, y) = (target_x - pos.0, target_y - pos.1);
+	let distance = x.hypot(y);
+	if distance <= max_speed {
+		(x, y)
+	} else {
+		(x / distance * max_speed, y / distance * max_speed)
+	}
+}
+
+/// Returns how many agents can be on a path at once, e.g., a corridor, keeping
+/// at least `agent_spacing` away from each other along it.
+///
+/// The path is measured as straight segments between its vertices, stretched
+/// as per [`Graph2D::scale`], with agents at both ends of it, so a path of a
+/// single vertex holds one agent, and an empty one none. Without any spacing
+/// to keep, there is no end to how many fit, and this saturates.
+pub fn path_throughput<G, V>(map: &G, path: &[&V], agent_spacing: f64) -> usize
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	if path.is_empty() {
+		return 0;
+	}
+	let (scale_x, scale_y) = map.scale();
+	let length: f64 = path
+		.windows(2)
+		.map(|step| {
+			let (x, y) = delta(step[0], step[1]);
+			(x * scale_x).hypot(y * scale_y)
+		})
+		.sum();
+	// Leave room for rounding errors, as spacings often split paths evenly
+	let gaps = (length / agent_spacing * (1.0 + 1e-9)).floor();
+	#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+	let gaps = gaps as usize;
+	gaps.saturating_add(1)
+}
+
+/// Returns the index of the vertex of a path from which an agent at
+/// `actual_pos` strayed further than `tolerance` from it, e.g., to replan from
+/// there, or `None` if it is still on track.
+///
+/// That is the vertex the segment of the path the agent is nearest to starts
+/// at, or the only vertex of a path made of just one. An empty path can't be
+/// strayed from.
+pub fn path_deviation<V: Vertex2D>(
+	path: &[&V],
+	actual_pos: (f64, f64),
+	tolerance: f64,
+) -> Option<usize> {
+	let points: Vec<_> = path.iter().map(|vertex| vertex.coords()).collect();
+	if points.is_empty() {
+		return None;
+	}
+	let (segment, _, distance) = nearest_point(&points, actual_pos);
+	(distance > tolerance).then_some(segment)
+}
+
+/// Returns how much of the cost of an annotated path is left to go once at
+/// its vertex at `current_index`, e.g., to show the distance left to walk.
+///
+/// The path is expected as returned by
+/// [`a_star_annotated`](crate::a_star_annotated), with the cost of reaching
+/// each vertex, so this takes constant time. Past the end of the path, or on
+/// an empty one, there is nothing left to go.
+pub fn remaining_cost<V>(path_with_costs: &[(&V, f64)], current_index: usize) -> f64 {
+	match (path_with_costs.last(), path_with_costs.get(current_index)) {
+		(Some(&(_, total)), Some(&(_, so_far))) => total - so_far,
+		_ => 0.0,
+	}
+}
+
+/// Returns a path as turtle graphics commands, i.e., how much to turn and then
+/// how far to move along each of its segments, e.g., to drive a robot.
+///
+/// Turns are in radians, within `[-π, π]`, and positive from the x axis
+/// towards the y axis. The robot is assumed to already head along the first
+/// segment, so it starts with no turn at all, and a segment of no length
+/// doesn't turn either, keeping the heading as it was. A path of one vertex
+/// or none has no segments.
+pub fn path_to_turtle<V: Vertex2D>(path: &[&V]) -> Vec<(f64, f64)> {
+	let mut heading: Option<(f64, f64)> = None;
+	let segments = path.windows(2).map(|segment| {
+		let (dx, dy) = delta(segment[0], segment[1]);
+		let distance = dx.hypot(dy);
+		if distance == 0.0 {
+			return (0.0, 0.0);
+		}
+		let turn = heading.map_or(0.0, |(heading_x, heading_y)| {
+			let cross = heading_x.mul_add(dy, -heading_y * dx);
+			cross.atan2(heading_x.mul_add(dx, heading_y * dy))
+		});
+		heading = Some((dx, dy));
+		(turn, distance)
+	});
+	segments.collect()
+}
+
+/// Returns the point `lookahead` further down the path from the point of it
+/// nearest to `pos`, or the end of the path if it isn't that long.
+fn lookahead_point(points: &[(f64, f64)], pos: (f64, f64), lookahead: f64) -> Option<(f64, f64)> {
+	let &last = points.last()?;
+	// Walk down the path from where it passes closest by
+	let (segment, mut from, _) = nearest_point(points, pos);
+	let mut remaining = lookahead.max(0.0);
+	for &to in &points[(segment + 1).min(points.len() - 1)..] {
+		let length = (to.0 - from.0).hypot(to.1 - from.1);
+		if length >= remaining && length > 0.0 {
+			return Some(lerp(from, to, remaining / length));
+		}
+		remaining -= length;
+		from = to;
+	}
+	Some(last)
+}
+
+/// Returns the point of a path nearest to `pos`, along with the index of the
+/// segment it lies on and how far from `pos` it is. The path may not be empty.
+fn nearest_point(points: &[(f64, f64)], pos: (f64, f64)) -> (usize, (f64, f64), f64) {
+	let distance = |point: (f64, f64)| (point.0 - pos.0).hypot(point.1 - pos.1);
+	let mut nearest = (0, points[0], distance(points[0]));
+	for (segment, step) in points.windows(2).enumerate() {
+		let point = nearest_on_segment(step[0], step[1], pos);
+		if distance(point) < nearest.2 {
+			nearest = (segment, point, distance(point));
+		}
+	}
+	nearest
+}
+
+/// Returns the point of the segment between `from` and `to` nearest to `pos`.
+fn nearest_on_segment(from: (f64, f64), to: (f64, f64), pos: (f64, f64)) -> (f64, f64) {
+	let (x, y) = (to.0 - from.0, to.1 - from.1);
+	let length_squared = x.mul_add(x, y * y);
+	if length_squared == 0.0 {
+		return from;
+	}
+	let along = (pos.0 - from.0).mul_add(x, (pos.1 - from.1) * y) / length_squared;
+	lerp(from, to, along.clamp(0.0, 1.0))
+}
+
+/// Interpolates linearly between two points.
+fn lerp(from: (f64, f64), to: (f64, f64), t: f64) -> (f64, f64) {
+	(
+		(to.0 - from.0).mul_add(t, from.0),
+		(to.1 - from.1).mul_add(t, from.1),
+	)
+}
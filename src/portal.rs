@@ -0,0 +1,118 @@
+//! Shortcuts between distant vertices of a graph, e.g., teleporters.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::{Graph2D, Vertex2D};
+
+/// A one-way edge between any two vertices, at a given cost.
+pub type Portal<'g, V> = (&'g V, &'g V, f64);
+
+/// A graph with extra portals, which lead from a vertex to another regardless
+/// of the edges of the graph itself.
+///
+/// Since a portal may cut a path short, the heuristic of the graph alone could
+/// overestimate. Instead, the estimate is the cheapest of heading straight to
+/// the goal, or of heading to the closest entrance, going through the
+/// cheapest portal, and on from the closest exit to the goal, all per the
+/// heuristic of the graph. Any path through portals, however many one after
+/// the other, costs at least as much, so the estimate stays admissible as long
+/// as the heuristic of the graph is.
+///
+/// A portal between two neighbors doesn't hide the edge between them: moving
+/// from one to the other costs whichever of the two is cheaper.
+pub struct PortalGraph<'g, G, V> {
+	graph: &'g G,
+	portals: Vec<Portal<'g, V>>,
+}
+
+impl<'g, G, V> PortalGraph<'g, G, V>
+where
+	G: Graph2D<V>,
+	V: PartialEq + Vertex2D,
+{
+	pub const fn new(graph: &'g G, portals: Vec<Portal<'g, V>>) -> Self {
+		Self { graph, portals }
+	}
+
+	/// Returns the cheapest portal from a vertex to another, if any.
+	fn portal(&self, vertex: &V, other: &V) -> Option<f64> {
+		self.portals
+			.iter()
+			.filter(|&&(from, to, _)| from == vertex && to == other)
+			.map(|&(_, _, cost)| cost)
+			.reduce(f64::min)
+	}
+
+	/// Whether the graph itself has a way from a vertex to its neighbor.
+	fn steps(&self, vertex: &V, other: &V) -> bool {
+		self.graph.neighbors(vertex).contains(&other)
+			&& self.graph.path_is_transversable(vertex, other)
+	}
+
+	/// Returns the cost of moving from a vertex to another, through the
+	/// cheapest portal between them or the edge of the graph itself, costing
+	/// `step`, whichever is cheaper.
+	fn cost(&self, vertex: &V, other: &V, step: impl FnOnce() -> f64) -> f64 {
+		match self.portal(vertex, other) {
+			Some(cost) if self.steps(vertex, other) => cost.min(step()),
+			Some(cost) => cost,
+			None => step(),
+		}
+	}
+}
+
+impl<G, V> Graph2D<V> for PortalGraph<'_, G, V>
+where
+	G: Graph2D<V>,
+	V: PartialEq + Vertex2D,
+{
+	crate::delegate_graph2d!(graph => V: has_vertex, scale);
+
+	fn neighbors(&self, vertex: &V) -> Vec<&V> {
+		let mut neighbors = self.graph.neighbors(vertex);
+		for &(from, to, _) in &self.portals {
+			if from == vertex && !neighbors.contains(&to) {
+				neighbors.push(to);
+			}
+		}
+		neighbors
+	}
+
+	fn path_is_transversable(&self, vertex: &V, other: &V) -> bool {
+		self.portal(vertex, other).is_some() || self.graph.path_is_transversable(vertex, other)
+	}
+
+	fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+		const INF: f64 = f64::INFINITY;
+		let portals = self.portals.iter();
+		let to_entrance = portals
+			.clone()
+			.map(|&(from, _, _)| self.graph.heuristic(vertex, from));
+		let through = portals.clone().map(|&(_, _, cost)| cost);
+		let from_exit = portals.map(|&(_, to, _)| self.graph.heuristic(to, other));
+		let through_portals = to_entrance.fold(INF, f64::min)
+			+ through.fold(INF, f64::min)
+			+ from_exit.fold(INF, f64::min);
+		self.graph.heuristic(vertex, other).min(through_portals)
+	}
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		self.cost(vertex, other, || self.graph.travel_cost(vertex, other))
+	}
+
+	fn travel_cost_with_context(&self, previous: Option<&V>, vertex: &V, other: &V) -> f64 {
+		self.cost(vertex, other, || {
+			self.graph.travel_cost_with_context(previous, vertex, other)
+		})
+	}
+
+	fn predecessors(&self, vertex: &V) -> Vec<&V> {
+		let mut predecessors = self.graph.predecessors(vertex);
+		for &(from, to, _) in &self.portals {
+			if to == vertex && !predecessors.contains(&from) {
+				predecessors.push(from);
+			}
+		}
+		predecessors
+	}
+}
@@ -0,0 +1,105 @@
+//! Graphs over points scattered around a continuous space, e.g., sampled at
+//! random, instead of laid out on a grid.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::{Graph2D, Vertex2D};
+
+use core::cmp::Ordering;
+
+/// A roadmap connecting every point to the points within a given radius of
+/// it, as the crow flies, at the cost of the distance between them.
+///
+/// When points are dense, each of them can have a great many neighbors, so
+/// [`with_k_nearest`](RoadmapGraph::with_k_nearest) can cap them to the
+/// closest ones for faster searches, at the expense of completeness.
+pub struct RoadmapGraph<V> {
+	points: Vec<V>,
+	radius: f64,
+	k_nearest: Option<usize>,
+}
+
+impl<V> RoadmapGraph<V>
+where
+	V: PartialEq + Vertex2D,
+{
+	#[must_use]
+	pub const fn new(points: Vec<V>, radius: f64) -> Self {
+		Self {
+			points,
+			radius,
+			k_nearest: None,
+		}
+	}
+
+	/// Only connects every point to the `k` closest points within the radius.
+	///
+	/// Roadmaps are sparse enough as it is, so too small a `k` easily leaves
+	/// out the one connection a path needed, e.g., crossing a gap to a point
+	/// farther away than a few others, and the search then finds no path at
+	/// all where there is one. Being close is not symmetric, so moving from a
+	/// point to another doesn't mean the way back is allowed too.
+	#[must_use]
+	pub const fn with_k_nearest(mut self, k: usize) -> Self {
+		self.k_nearest = Some(k);
+		self
+	}
+
+	/// The points of the roadmap, in the order they were given.
+	#[must_use]
+	pub fn points(&self) -> &[V] {
+		&self.points
+	}
+
+	/// Returns the points within the radius of a vertex, closest first,
+	/// capped to the `k` nearest ones if so configured.
+	fn connections(&self, vertex: &V) -> Vec<&V> {
+		let mut nearby: Vec<_> = self
+			.points
+			.iter()
+			.filter(|&point| point != vertex)
+			.map(|point| (point, vertex.euclidean_distance(point)))
+			.filter(|&(_, distance)| distance <= self.radius)
+			.collect();
+		nearby.sort_by(|(_, distance), (_, other)| {
+			distance.partial_cmp(other).unwrap_or(Ordering::Equal)
+		});
+		nearby.truncate(self.k_nearest.unwrap_or(usize::MAX));
+		nearby.into_iter().map(|(point, _)| point).collect()
+	}
+}
+
+impl<V> Graph2D<V> for RoadmapGraph<V>
+where
+	V: PartialEq + Vertex2D,
+{
+	fn neighbors(&self, vertex: &V) -> Vec<&V> {
+		self.connections(vertex)
+	}
+
+	fn path_is_transversable(&self, vertex: &V, other: &V) -> bool {
+		vertex.euclidean_distance(other) <= self.radius
+	}
+
+	fn has_vertex(&self, vertex: &V) -> bool {
+		self.points.contains(vertex)
+	}
+
+	fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+		vertex.euclidean_distance(other)
+	}
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		vertex.euclidean_distance(other)
+	}
+
+	fn predecessors(&self, vertex: &V) -> Vec<&V> {
+		if self.k_nearest.is_none() {
+			return self.connections(vertex);
+		}
+		let predecessors = self.points.iter();
+		predecessors
+			.filter(|&point| self.connections(point).contains(&vertex))
+			.collect()
+	}
+}
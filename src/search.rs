@@ -0,0 +1,336 @@
+//! The bookkeeping behind every search: which nodes are yet to be analysed,
+//! and how each node seen so far was reached.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::{vec, Vec};
+use crate::compat::{BinaryHeap, HashMap, HashSet};
+use crate::Vertex2D;
+
+use core::cmp::Ordering;
+use core::hash::Hash;
+
+pub struct NodeInfo<N> {
+	pub parent: Option<N>,
+	pub g_score: f64,
+	pub f_score: f64,
+	/// How many hops away from the start this node was reached.
+	pub depth: usize,
+}
+
+impl<N> Default for NodeInfo<N> {
+	fn default() -> Self {
+		const INF: f64 = f64::INFINITY;
+		Self {
+			parent: None,
+			g_score: INF,
+			f_score: INF,
+			depth: 0,
+		}
+	}
+}
+
+/// Orders vertices by their coordinates, `x` first.
+pub fn cmp_coords<V: Vertex2D + ?Sized>(vertex: &V, other: &V) -> Ordering {
+	let (x, y) = vertex.coords();
+	let (other_x, other_y) = other.coords();
+	x.partial_cmp(&other_x)
+		.unwrap_or(Ordering::Equal)
+		.then_with(|| y.partial_cmp(&other_y).unwrap_or(Ordering::Equal))
+}
+
+/// Orders nodes by where they are, so that equally promising ones are always
+/// analysed in the same order, rather than in whatever order they are hashed.
+pub trait Placed {
+	fn cmp_place(&self, other: &Self) -> Ordering;
+}
+
+impl<V: Vertex2D + ?Sized> Placed for &V {
+	fn cmp_place(&self, other: &Self) -> Ordering {
+		cmp_coords(*self, *other)
+	}
+}
+
+/// A vertex at a given tick, earlier ticks first.
+impl<V: Vertex2D> Placed for (&V, usize) {
+	fn cmp_place(&self, other: &Self) -> Ordering {
+		cmp_coords(self.0, other.0).then(self.1.cmp(&other.1))
+	}
+}
+
+/// A vertex along with where it was reached from, if anywhere.
+impl<V: Vertex2D> Placed for (Option<&V>, &V) {
+	fn cmp_place(&self, other: &Self) -> Ordering {
+		let previous = match (self.0, other.0) {
+			(Some(previous), Some(other)) => cmp_coords(previous, other),
+			(previous, other) => previous.is_some().cmp(&other.is_some()),
+		};
+		cmp_coords(self.1, other.1).then(previous)
+	}
+}
+
+/// A vertex kept elsewhere, by where it is stored, i.e., in the order it was
+/// first come across.
+impl Placed for usize {
+	fn cmp_place(&self, other: &Self) -> Ordering {
+		self.cmp(other)
+	}
+}
+
+/// A node queued up in a heap along with its f score at the time, the lowest
+/// score and the node placed first coming out on top.
+struct Queued<N> {
+	node: N,
+	f_score: f64,
+}
+
+impl<N: Placed> Ord for Queued<N> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		let f_score = other
+			.f_score
+			.partial_cmp(&self.f_score)
+			.unwrap_or(Ordering::Equal);
+		f_score.then_with(|| other.node.cmp_place(&self.node))
+	}
+}
+
+impl<N: Placed> PartialOrd for Queued<N> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<N: Placed> PartialEq for Queued<N> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other).is_eq()
+	}
+}
+
+impl<N: Placed> Eq for Queued<N> {}
+
+/// The bookkeeping shared by every search in this crate: the open list of
+/// nodes still to be analysed and the scores of every node seen so far.
+///
+/// Nodes are usually references to the vertices of a graph, but may also be
+/// any other state derived from them, e.g., a vertex at a given time.
+///
+/// Finding the most promising node in the open list means looking at all of
+/// them, unless the search is told to [keep a heap](Search::use_heap) of them
+/// as well, which pays off on large frontiers.
+pub struct Search<N> {
+	open_list: HashSet<N>,
+	node_info: HashMap<N, NodeInfo<N>>,
+	/// Every node ever queued up, including those since expanded or queued up
+	/// again with another score, which are skipped once they come out.
+	heap: Option<BinaryHeap<Queued<N>>>,
+}
+
+impl<'m, V> Search<&'m V>
+where
+	V: Hash + Eq + Vertex2D + ?Sized,
+{
+	/// Prepares a search from `start` towards `goal`, with the start node
+	/// already in the open list.
+	pub fn new(start: &'m V, goal: &V, start_h: f64) -> Self {
+		let mut search = Self::between(start, goal);
+		// The start node has a zero cost to move to, and given f = g + h,
+		// initialize that score to the heuristics alone
+		search.open(start, None, 0.0, start_h);
+		search
+	}
+
+	/// Prepares an empty search from `start` towards `goal`.
+	pub fn between(start: &V, goal: &V) -> Self {
+		// Estimate how many nodes need to be visited between start and goal
+		// to lower the number of allocations required
+		let (start_x, start_y) = start.coords();
+		let (goal_x, goal_y) = goal.coords();
+		let dx = (goal_x - start_x).abs().max(1.0);
+		let dy = (goal_y - start_y).abs().max(1.0);
+		Self::with_capacity(estimate(dx * dy), estimate(2.0 * (dx + dy)))
+	}
+}
+
+impl<N> Search<N>
+where
+	N: Copy + Hash + Eq + Placed,
+{
+	pub fn with_capacity(visits: usize, analysis: usize) -> Self {
+		Self {
+			open_list: HashSet::with_capacity(analysis),
+			node_info: HashMap::with_capacity(visits),
+			heap: None,
+		}
+	}
+
+	/// Keeps the open list in a heap from now on as well, so that nodes are
+	/// popped off it in logarithmic time, rather than linear. They come out in
+	/// the very same order either way.
+	pub fn use_heap(&mut self) {
+		let node_info = &self.node_info;
+		let queued = self.open_list.iter().map(|&node| Queued {
+			node,
+			f_score: node_info
+				.get(&node)
+				.map_or(f64::INFINITY, |info| info.f_score),
+		});
+		self.heap = Some(queued.collect());
+	}
+
+	/// Records the scores of `node`, reached through `parent`, and queues it
+	/// for analysis.
+	pub fn open(&mut self, node: N, parent: Option<N>, g_score: f64, f_score: f64) {
+		self.record(node, parent, g_score, f_score);
+		self.open_list.insert(node);
+		if let Some(heap) = &mut self.heap {
+			heap.push(Queued { node, f_score });
+		}
+	}
+
+	/// Records the scores of `node`, reached through `parent`, without
+	/// queueing it for analysis.
+	pub fn record(&mut self, node: N, parent: Option<N>, g_score: f64, f_score: f64) {
+		let depth = parent.map_or(0, |parent| self.info(parent).depth + 1);
+		self.node_info.insert(
+			node,
+			NodeInfo {
+				parent,
+				g_score,
+				f_score,
+				depth,
+			},
+		);
+	}
+
+	/// Removes and returns the node in the open list with the lowest f score,
+	/// ties going to the node placed first.
+	pub fn pop(&mut self) -> Option<N> {
+		if let Some(heap) = &mut self.heap {
+			while let Some(Queued { node, f_score }) = heap.pop() {
+				// Skip nodes since expanded, or queued up again with another
+				// score, whose later entries are the ones that count
+				let current = self.node_info.get(&node).map(|info| info.f_score.to_bits());
+				if self.open_list.contains(&node) && current == Some(f_score.to_bits()) {
+					self.open_list.remove(&node);
+					return Some(node);
+				}
+			}
+			return None;
+		}
+		let node_info = &self.node_info;
+		let f_score = |node: N| {
+			node_info
+				.get(&node)
+				.map_or(f64::INFINITY, |info| info.f_score)
+		};
+		let mut list = self.open_list.iter();
+		let cmp_node = list.next()?;
+		let node = list.fold(*cmp_node, |acc, &node| {
+			let (acc_f, node_f) = (f_score(acc), f_score(node));
+			if acc_f < node_f || (acc_f <= node_f && acc.cmp_place(&node).is_le()) {
+				acc
+			} else {
+				node
+			}
+		});
+		self.open_list.remove(&node);
+		Some(node)
+	}
+
+	/// Iterates over the nodes still in the open list, in no particular order.
+	pub fn open_nodes(&self) -> impl Iterator<Item = N> + '_ {
+		self.open_list.iter().copied()
+	}
+
+	/// How many nodes are still in the open list.
+	pub fn open_len(&self) -> usize {
+		self.open_list.len()
+	}
+
+	/// Iterates over every node seen so far that was reached through another
+	/// one, along with that parent, in no particular order.
+	pub fn parents(&self) -> impl Iterator<Item = (N, N)> + '_ {
+		let parents = self.node_info.iter();
+		parents.filter_map(|(&node, info)| info.parent.map(|parent| (node, parent)))
+	}
+
+	/// Whether a node was seen and is no longer in the open list, i.e., it
+	/// was already analysed.
+	pub fn is_closed(&self, node: N) -> bool {
+		self.node_info.contains_key(&node) && !self.open_list.contains(&node)
+	}
+
+	/// How many nodes were seen so far, analysed or not.
+	pub fn seen(&self) -> usize {
+		self.node_info.len()
+	}
+
+	/// Returns the scores of a node, which is expected to have been seen, or
+	/// records the defaults for it if it never was.
+	pub fn info(&mut self, node: N) -> &NodeInfo<N> {
+		self.node_info.entry(node).or_default()
+	}
+
+	/// Relaxes the edge between `node` and `neighbor`: if going through `node`
+	/// is cheaper than any previously known way of reaching `neighbor`, it
+	/// becomes its parent and the neighbor is queued for analysis. Returns
+	/// whether the neighbor was updated.
+	pub fn relax(&mut self, node: N, neighbor: N, cost: f64, h: f64) -> bool {
+		// Looking the neighbor up mustn't record it, or one behind an edge
+		// that can't be taken would pass for expanded
+		let new_g = self.g_score(node) + cost;
+		if self.g_score(neighbor) > new_g {
+			self.open(neighbor, Some(node), new_g, new_g + h);
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Walks the parents of `node` back to the start, returning the nodes in
+	/// the order they were visited, i.e., from `node` to the start.
+	pub fn path_from(&self, node: N) -> Vec<N> {
+		let mut path = vec![node];
+		let mut cur_node = node;
+		while let Some(parent) = self.node_info.get(&cur_node).and_then(|info| info.parent) {
+			cur_node = parent;
+			path.push(cur_node);
+		}
+		path
+	}
+
+	/// Builds the path from the start to `node`.
+	pub fn path_to(&self, node: N) -> Vec<N> {
+		let mut path = self.path_from(node);
+		// Since the path is built from last to finish, we must reverse it
+		// before returning it.
+		path.reverse();
+		path
+	}
+
+	/// Returns the estimated cost of a path through a node, or infinity if it
+	/// was never seen.
+	pub fn f_score(&self, node: N) -> f64 {
+		self.node_info
+			.get(&node)
+			.map_or(f64::INFINITY, |info| info.f_score)
+	}
+
+	/// Returns the cost of reaching a node from the start, or infinity if it
+	/// was never seen.
+	pub fn g_score(&self, node: N) -> f64 {
+		self.node_info
+			.get(&node)
+			.map_or(f64::INFINITY, |info| info.g_score)
+	}
+}
+
+/// Converts an estimated amount of nodes into a capacity, falling back to no
+/// preallocation at all if the estimate makes no sense.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn estimate(amount: f64) -> usize {
+	if amount.is_normal() && amount > 0.0 {
+		amount as usize
+	} else {
+		0
+	}
+}
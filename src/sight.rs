@@ -0,0 +1,81 @@
+//! Straight lines across a graph, e.g., to tell which vertices of a path can
+//! be skipped by heading straight to a later one.
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::compat::Float;
+use crate::search::cmp_coords;
+use crate::{Graph2D, Vertex2D};
+
+use core::cmp::Ordering;
+
+/// Returns whether `to` can be seen from `from`, i.e., whether the straight
+/// line between them can be walked along the edges of the graph.
+///
+/// The line is followed one step at a time, always to the neighbor lying the
+/// closest to it among those getting any closer to `to`, which may stray from
+/// it by no more than half of that step. Whether vertices are seen depends on
+/// how the graph is laid out, so on a square grid, only the cells the line
+/// runs through are walked, and it can't squeeze past a wall any more than a
+/// search could.
+pub fn line_of_sight<G, V>(map: &G, from: &V, to: &V) -> bool
+where
+	G: Graph2D<V>,
+	V: PartialEq + Vertex2D,
+{
+	let ((x, y), (to_x, to_y)) = (from.coords(), to.coords());
+	let length = (to_x - x).hypot(to_y - y);
+	// How far away a vertex lies from the line, measured across it
+	let off_line = |vertex: &V| {
+		if length == 0.0 {
+			return vertex.euclidean_distance(from);
+		}
+		let (vertex_x, vertex_y) = vertex.coords();
+		(to_x - x)
+			.mul_add(vertex_y - y, -(to_y - y) * (vertex_x - x))
+			.abs() / length
+	};
+	let mut cur_node = from;
+	while cur_node != to {
+		let remaining = cur_node.euclidean_distance(to);
+		let next = map
+			.neighbors(cur_node)
+			.into_iter()
+			.filter(|&neighbor| neighbor.euclidean_distance(to) < remaining)
+			.min_by(|&neighbor, &other| {
+				let order = off_line(neighbor)
+					.partial_cmp(&off_line(other))
+					.unwrap_or(Ordering::Equal);
+				order.then_with(|| cmp_coords(neighbor, other))
+			});
+		match next {
+			Some(next)
+				if off_line(next) <= cur_node.euclidean_distance(next) / 2.0
+					&& map.path_is_transversable(cur_node, next) =>
+			{
+				cur_node = next;
+			}
+			_ => return false,
+		}
+	}
+	true
+}
+
+/// Returns the index of the furthest vertex of a path, from `start_idx`
+/// onwards, up to which every vertex can be seen from `from`, so the path can
+/// be cut short by heading straight there.
+///
+/// This is what string pulling smooths paths with. The vertex at `start_idx`
+/// is assumed to be seen, usually being the next one along the path, and the
+/// index is clamped to the path.
+pub fn furthest_visible<G, V>(map: &G, from: &V, path: &[&V], start_idx: usize) -> usize
+where
+	G: Graph2D<V>,
+	V: PartialEq + Vertex2D,
+{
+	let start_idx = start_idx.min(path.len().saturating_sub(1));
+	let seen = path
+		.iter()
+		.skip(start_idx + 1)
+		.take_while(|vertex| line_of_sight(map, from, vertex));
+	start_idx + seen.count()
+}
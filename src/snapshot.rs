@@ -0,0 +1,86 @@
+//! Changes to a graph proposed while it is being searched.
+//!
+//! Searches borrow their graph immutably for as long as they run, so the
+//! graph can't be changed from within a callback, e.g., a filter or a bias,
+//! that notices something about it. Instead, a [`SnapshotGraph`] lets those
+//! callbacks queue changes up, which every search ignores, and that only take
+//! effect once they are [committed](SnapshotGraph::commit) in between
+//! searches, when nothing borrows the graph anymore.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::{Box, Vec};
+use crate::{Graph2D, Vertex2D};
+
+use core::cell::RefCell;
+use core::ops::{Deref, DerefMut};
+
+/// A change to be made to a graph later on.
+type Change<G> = Box<dyn FnOnce(&mut G)>;
+
+/// A graph that can be searched as it is while changes to it are proposed.
+pub struct SnapshotGraph<G> {
+	graph: G,
+	pending: RefCell<Vec<Change<G>>>,
+}
+
+impl<G> SnapshotGraph<G> {
+	pub fn new(graph: G) -> Self {
+		Self {
+			graph,
+			pending: RefCell::new(Vec::new()),
+		}
+	}
+
+	/// Queues a change up, to be made on the next
+	/// [`commit`](SnapshotGraph::commit). Searches running in the meantime
+	/// keep seeing the graph as it was.
+	pub fn propose<F: FnOnce(&mut G) + 'static>(&self, change: F) {
+		self.pending.borrow_mut().push(Box::new(change));
+	}
+
+	/// How many changes are waiting to be committed.
+	pub fn pending(&self) -> usize {
+		self.pending.borrow().len()
+	}
+
+	/// Makes every proposed change, in the order they were proposed, and
+	/// returns how many there were.
+	pub fn commit(&mut self) -> usize {
+		let pending = self.pending.get_mut().split_off(0);
+		let count = pending.len();
+		for change in pending {
+			change(&mut self.graph);
+		}
+		count
+	}
+
+	/// Unwraps the graph, dropping any changes that weren't committed.
+	pub fn into_inner(self) -> G {
+		self.graph
+	}
+}
+
+impl<G> Deref for SnapshotGraph<G> {
+	type Target = G;
+
+	fn deref(&self) -> &G {
+		&self.graph
+	}
+}
+
+impl<G> DerefMut for SnapshotGraph<G> {
+	fn deref_mut(&mut self) -> &mut G {
+		&mut self.graph
+	}
+}
+
+impl<G, V> Graph2D<V> for SnapshotGraph<G>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	crate::delegate_graph2d!(
+		graph => V: neighbors, path_is_transversable, has_vertex, heuristic, travel_cost,
+		travel_cost_with_context, predecessors, scale
+	);
+}
@@ -0,0 +1,180 @@
+//! Measurements of how much work searches do, e.g., to compare heuristics, and
+//! records of every step they take along the way.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::search::Search;
+use crate::{run_observed, Graph2D, Rules, Vertex2D};
+
+use core::cell::RefCell;
+use core::hash::Hash;
+use core::ops::ControlFlow;
+
+/// How much work a search did.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStats {
+	/// How many vertices had their neighbors looked at.
+	pub expanded: usize,
+	/// How many vertices were seen at all, expanded or not.
+	pub seen: usize,
+	/// How many vertices expanded had no edge out of them that could be
+	/// taken, i.e., were dead ends, which in a graph whose edges go both ways
+	/// gives away a bug in it.
+	pub dead_ends: usize,
+}
+
+/// Same as [`a_star`](crate::a_star), but also returns how much work the
+/// search did, whether it found a path or not.
+pub fn a_star_with_stats<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+) -> (Option<Vec<&'m V>>, SearchStats)
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let mut stats = SearchStats::default();
+	let mut observe = |node: &'m V, search: &Search<&'m V>| {
+		stats.expanded += 1;
+		stats.seen = search.seen();
+		let neighbors = map.neighbors(node);
+		if !neighbors
+			.into_iter()
+			.any(|neighbor| map.path_is_transversable(node, neighbor))
+		{
+			stats.dead_ends += 1;
+		}
+		ControlFlow::Continue(())
+	};
+	let search = run_observed(map, &[(start, 0.0)], goal, &Rules::default(), &mut observe);
+	if let Some(search) = &search {
+		stats.seen = search.seen();
+	}
+	(search.map(|search| search.path_to(goal)), stats)
+}
+
+/// Returns how many vertices the search expands for each vertex of the path it
+/// finds, the goal counting as expanded once reached, e.g., to tell how good a
+/// heuristic is.
+///
+/// A perfect heuristic only ever expands the vertices of the path, for a ratio
+/// of `1.0`, and the worse the heuristic, the higher the ratio. If there is no
+/// path, the search was all for nothing, and the ratio is infinite.
+pub fn search_efficiency<G, V>(map: &G, start: &V, goal: &V) -> f64
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	match a_star_with_stats(map, start, goal) {
+		#[allow(clippy::cast_precision_loss)]
+		(Some(path), stats) => (stats.expanded + 1) as f64 / path.len() as f64,
+		(None, _) => f64::INFINITY,
+	}
+}
+
+/// A step of a search, as recorded in a [`SearchLog`], i.e., a vertex being
+/// expanded, followed by how far along the search is, or one of its edges
+/// being relaxed. Vertices are given by their coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchEvent {
+	/// A vertex was taken off the open list to have its neighbors looked at.
+	Expanded {
+		coords: (f64, f64),
+		g_score: f64,
+		f_score: f64,
+	},
+	/// The edge from a vertex to its neighbor was considered, leading to the
+	/// neighbor at `g_score`, which may have `improved` on the way to it known
+	/// so far.
+	Relaxed {
+		from: (f64, f64),
+		to: (f64, f64),
+		g_score: f64,
+		improved: bool,
+	},
+	/// How far along the search is after an expansion, from `0` to `1`.
+	///
+	/// This is only an estimate, going by how close to the goal the search
+	/// has come as far as the heuristic can tell, i.e., `1 - h / h₀`, `h` being
+	/// the lowest heuristic of the vertices expanded so far, and `h₀` that of
+	/// the start. It never goes down, but may well stall for a while whenever
+	/// a wall is in the way.
+	Progress(f64),
+}
+
+/// Every step a search took, in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchLog {
+	events: Vec<SearchEvent>,
+}
+
+impl SearchLog {
+	#[must_use]
+	pub fn events(&self) -> &[SearchEvent] {
+		&self.events
+	}
+}
+
+/// Same as [`a_star`](crate::a_star), but also records every step the search
+/// takes, whether it found a path or not, e.g., to tell where two searches
+/// that should behave the same part ways.
+pub fn a_star_record<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+) -> (Option<Vec<&'m V>>, SearchLog)
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let events = RefCell::new(Vec::new());
+	let watch = |from: &V, to: &V, g_score, improved| {
+		let (from, to) = (from.coords(), to.coords());
+		events.borrow_mut().push(SearchEvent::Relaxed {
+			from,
+			to,
+			g_score,
+			improved,
+		});
+	};
+	let rules = Rules {
+		watch: Some(&watch),
+		..Rules::default()
+	};
+	let start_h = map.heuristic(start, goal);
+	let mut best_h = start_h;
+	let mut observe = |node: &'m V, search: &Search<&'m V>| {
+		best_h = best_h.min(map.heuristic(node, goal));
+		let mut events = events.borrow_mut();
+		events.push(SearchEvent::Expanded {
+			coords: node.coords(),
+			g_score: search.g_score(node),
+			f_score: search.f_score(node),
+		});
+		events.push(SearchEvent::Progress(progress(best_h, start_h)));
+		ControlFlow::Continue(())
+	};
+	let search = run_observed(map, &[(start, 0.0)], goal, &rules, &mut observe);
+	let log = SearchLog {
+		events: events.into_inner(),
+	};
+	(search.map(|search| search.path_to(goal)), log)
+}
+
+/// Returns whether two logs hold exactly the same steps, scores included,
+/// e.g., to make sure a change to a search keeps it behaving the same.
+#[must_use]
+pub fn replay_matches(log: &SearchLog, fresh_log: &SearchLog) -> bool {
+	log == fresh_log
+}
+
+/// Returns how far along a search is, given the lowest heuristic it has come
+/// up with so far and that of the start, clamped between `0` and `1`.
+pub(crate) fn progress(h: f64, start_h: f64) -> f64 {
+	if start_h > 0.0 {
+		(1.0 - h / start_h).clamp(0.0, 1.0)
+	} else {
+		1.0
+	}
+}
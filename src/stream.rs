@@ -0,0 +1,69 @@
+//! Searches that report how they are getting along over a channel, so that
+//! another thread, e.g., a server or a user interface, can follow them live.
+//!
+//! Channels come from the standard library, so this module is only available
+//! along with it.
+
+use crate::search::Search;
+use crate::stats::progress;
+use crate::{run_observed, Graph2D, Rules, Vertex2D};
+
+use core::hash::Hash;
+use core::ops::ControlFlow;
+use std::sync::mpsc::Sender;
+
+/// Something a search streamed has done, sent as soon as it happens.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StreamEvent<'m, V> {
+	/// A vertex was taken off the frontier to have its neighbors looked at.
+	Expanded(&'m V),
+	/// How many vertices are left on the frontier after an expansion.
+	FrontierSize(usize),
+	/// How far along the search is after an expansion, from `0` to `1`, as
+	/// [estimated](crate::stats::SearchEvent::Progress) by the heuristic.
+	Progress(f64),
+	/// The search is over, having found this path.
+	Found(Vec<&'m V>),
+	/// The search is over, there being no path.
+	Failed,
+}
+
+/// Same as [`a_star`](crate::a_star), but streams every expansion over a
+/// channel as the search runs, followed by its outcome.
+///
+/// Each vertex expanded is sent along with the size of the frontier right
+/// after and an estimate of the progress made, and the last event is always
+/// either [`Found`](StreamEvent::Found) or [`Failed`](StreamEvent::Failed).
+/// If the receiving end hangs up, no one is interested in the search anymore,
+/// so it is given up on at once.
+pub fn a_star_stream<'m, G, V>(
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	tx: &Sender<StreamEvent<'m, V>>,
+) where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	let start_h = map.heuristic(start, goal);
+	let mut best_h = start_h;
+	let mut observe = |node: &'m V, search: &Search<&'m V>| {
+		best_h = best_h.min(map.heuristic(node, goal));
+		let sent = tx
+			.send(StreamEvent::Expanded(node))
+			.and_then(|()| tx.send(StreamEvent::FrontierSize(search.open_len())))
+			.and_then(|()| tx.send(StreamEvent::Progress(progress(best_h, start_h))));
+		if sent.is_ok() {
+			ControlFlow::Continue(())
+		} else {
+			ControlFlow::Break(())
+		}
+	};
+	let search = run_observed(map, &[(start, 0.0)], goal, &Rules::default(), &mut observe);
+	let outcome = search.map_or(StreamEvent::Failed, |search| {
+		StreamEvent::Found(search.path_to(goal))
+	});
+	// Nobody may be listening anymore, which is fine since there is nothing
+	// left to do either way
+	let _ = tx.send(outcome);
+}
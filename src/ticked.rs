@@ -0,0 +1,120 @@
+//! Searches spread over several ticks of a simulation, e.g., the frames of a
+//! game, during which the graph may change slightly.
+
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::search::Search;
+use crate::{neighbors, Graph2D, Vertex2D};
+
+use core::hash::Hash;
+use core::task::Poll;
+
+/// A search that only goes as far as it is told to at a time, picking up
+/// where it left off on the next tick.
+///
+/// In between ticks, the graph may change, as long as it can do so while
+/// borrowed, e.g., through a `RefCell`, and each edge that did is reported to
+/// [`edge_changed`](TickedSearch::edge_changed). Most changes can be taken
+/// into account without starting over, but those under the path already
+/// found to some vertex can't, and restart the search from scratch.
+pub struct TickedSearch<'m, G, V> {
+	map: &'m G,
+	start: &'m V,
+	goal: &'m V,
+	search: Search<&'m V>,
+	outcome: Poll<Option<Vec<&'m V>>>,
+}
+
+impl<'m, G, V> TickedSearch<'m, G, V>
+where
+	G: Graph2D<V>,
+	V: Hash + Eq + Vertex2D,
+{
+	/// Prepares a search from `start` towards `goal`, without expanding
+	/// anything yet.
+	pub fn new(map: &'m G, start: &'m V, goal: &'m V) -> Self {
+		Self {
+			map,
+			start,
+			goal,
+			search: Search::new(start, goal, map.heuristic(start, goal)),
+			outcome: Poll::Pending,
+		}
+	}
+
+	/// Expands up to `expansions` more vertices, returning the path once the
+	/// search is over, `None` if there is none, and [`Pending`](Poll::Pending)
+	/// until then. A search that is over keeps
+	/// returning the same outcome, until the graph changes under it.
+	pub fn advance(&mut self, expansions: usize) -> Poll<Option<Vec<&'m V>>> {
+		if self.outcome.is_ready() {
+			return self.outcome.clone();
+		}
+		for _ in 0..expansions {
+			let Some(cur_node) = self.search.pop() else {
+				return self.finish(None);
+			};
+			if cur_node == self.goal {
+				return self.finish(Some(self.search.path_to(cur_node)));
+			}
+			let previous = self.search.info(cur_node).parent;
+			for neighbor in neighbors(self.map, cur_node) {
+				self.relax(previous, cur_node, neighbor);
+			}
+		}
+		Poll::Pending
+	}
+
+	/// Takes into account that the edge from `vertex` to `other` changed, be
+	/// it its cost or whether it can be taken at all. Changes to edges both
+	/// ways are reported once each way.
+	///
+	/// The edge only matters if `vertex` was expanded already. If `other` was
+	/// reached through it, everything reached past it may be off, so the
+	/// search starts over. Otherwise, the edge is just looked at again, in
+	/// case it has become the cheaper way to `other`.
+	pub fn edge_changed(&mut self, vertex: &'m V, other: &'m V) {
+		if !self.search.is_closed(vertex) {
+			return;
+		}
+		if self.search.g_score(other).is_finite() && self.search.info(other).parent == Some(vertex)
+		{
+			self.restart();
+			return;
+		}
+		let previous = self.search.info(vertex).parent;
+		if self.relax(previous, vertex, other) {
+			// The path found may not be the cheapest one anymore, so the goal
+			// has to be reached all over again
+			if let Poll::Ready(Some(_)) = core::mem::replace(&mut self.outcome, Poll::Pending) {
+				let goal = self.search.info(self.goal);
+				let (parent, g_score, f_score) = (goal.parent, goal.g_score, goal.f_score);
+				self.search.open(self.goal, parent, g_score, f_score);
+			}
+		}
+	}
+
+	/// Throws everything found so far away, to search again from the start.
+	pub fn restart(&mut self) {
+		let map = self.map;
+		self.search = Search::new(self.start, self.goal, map.heuristic(self.start, self.goal));
+		self.outcome = Poll::Pending;
+	}
+
+	/// Relaxes the edge from `vertex` to `other`, if it can be taken, returning
+	/// whether it led to `other` any cheaper.
+	fn relax(&mut self, previous: Option<&'m V>, vertex: &'m V, other: &'m V) -> bool {
+		if !self.map.path_is_transversable(vertex, other) {
+			return false;
+		}
+		let cost = self.map.travel_cost_with_context(previous, vertex, other);
+		let h = self.map.heuristic(other, self.goal);
+		self.search.relax(vertex, other, cost, h)
+	}
+
+	/// Records how the search ended, to keep returning it from then on.
+	fn finish(&mut self, outcome: Option<Vec<&'m V>>) -> Poll<Option<Vec<&'m V>>> {
+		self.outcome = Poll::Ready(outcome.clone());
+		Poll::Ready(outcome)
+	}
+}
@@ -0,0 +1,75 @@
+//! Graphs whose edges are guaranteed to go both ways, for the algorithms that
+//! assume as much, e.g., [`bidirectional_a_star`](crate::bidirectional_a_star)
+//! or [`cost_field`](crate::field::cost_field).
+
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
+use crate::{Graph2D, Vertex2D};
+
+/// A graph in which every edge of the graph it wraps can also be taken the
+/// other way around.
+///
+/// Vertices neighbor the ones they lead to as well as the ones leading to
+/// them, as told by [`Graph2D::predecessors`], and a move is possible if it is
+/// either way. It costs as much as the cheapest way it can be taken, so the
+/// cost of an edge only one way is kept as is, while that of an edge both ways
+/// is the smallest of the two. Costs depending on how a vertex was reached
+/// have no meaning backwards, so they are left out.
+///
+/// The heuristic is likewise the smallest of both ways, but since edges may
+/// now be taken backwards, it may overestimate where they cut a path short.
+pub struct Undirected<'g, G> {
+	graph: &'g G,
+}
+
+impl<'g, G> Undirected<'g, G> {
+	pub const fn new(graph: &'g G) -> Self {
+		Self { graph }
+	}
+}
+
+impl<G, V> Graph2D<V> for Undirected<'_, G>
+where
+	G: Graph2D<V>,
+	V: PartialEq + Vertex2D,
+{
+	crate::delegate_graph2d!(graph => V: has_vertex, scale);
+
+	fn neighbors(&self, vertex: &V) -> Vec<&V> {
+		let mut neighbors = self.graph.neighbors(vertex);
+		for predecessor in self.graph.predecessors(vertex) {
+			if !neighbors.contains(&predecessor) {
+				neighbors.push(predecessor);
+			}
+		}
+		neighbors
+	}
+
+	fn path_is_transversable(&self, vertex: &V, other: &V) -> bool {
+		self.graph.path_is_transversable(vertex, other)
+			|| self.graph.path_is_transversable(other, vertex)
+	}
+
+	fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+		self.graph
+			.heuristic(vertex, other)
+			.min(self.graph.heuristic(other, vertex))
+	}
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		let forwards = self.graph.path_is_transversable(vertex, other);
+		let backwards = self.graph.path_is_transversable(other, vertex);
+		match (forwards, backwards) {
+			(true, false) => self.graph.travel_cost(vertex, other),
+			(false, true) => self.graph.travel_cost(other, vertex),
+			_ => self
+				.graph
+				.travel_cost(vertex, other)
+				.min(self.graph.travel_cost(other, vertex)),
+		}
+	}
+
+	fn predecessors(&self, vertex: &V) -> Vec<&V> {
+		self.neighbors(vertex)
+	}
+}
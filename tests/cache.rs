@@ -0,0 +1,33 @@
+mod common;
+
+use a_star::cache::CachedPathfinder;
+use common::Grid;
+
+#[test]
+fn repeated_queries_are_served_from_the_cache() {
+	let paths = CachedPathfinder::new(Grid::parse(&["....", ".##.", "...."]), 2);
+	let (start, goal) = (paths.at(0, 1).clone(), paths.at(3, 1).clone());
+	let first = paths.path(&start, &goal).unwrap();
+	assert_eq!((paths.hits(), paths.misses()), (0, 1));
+	assert_eq!(paths.path(&start, &goal).unwrap(), first);
+	assert_eq!((paths.hits(), paths.misses()), (1, 1));
+	paths.invalidate();
+	paths.path(&start, &goal).unwrap();
+	assert_eq!((paths.hits(), paths.misses()), (1, 2));
+}
+
+#[test]
+fn least_recently_used_paths_are_forgotten_first() {
+	let paths = CachedPathfinder::new(Grid::open(4, 4), 2);
+	let cell = |x, y| paths.at(x, y).clone();
+	let (a, b, c) = ((cell(0, 0), cell(3, 3)), (cell(0, 3), cell(3, 0)), (cell(1, 1), cell(2, 2)));
+	for query in &[&a, &b, &a, &c] {
+		paths.path(&query.0, &query.1);
+	}
+	assert_eq!(paths.misses(), 3);
+	// The first query was used more recently than the second one
+	paths.path(&a.0, &a.1);
+	assert_eq!(paths.misses(), 3);
+	paths.path(&b.0, &b.1);
+	assert_eq!(paths.misses(), 4);
+}
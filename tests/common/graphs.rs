@@ -0,0 +1,162 @@
+//! Graphs the tests build on top of others, or out of a handful of vertices,
+//! rather than each test writing its own.
+
+use super::Cell;
+
+use a_star::{Graph2D, Vertex2D};
+
+type Neighbors<'t, G, V> = Box<dyn for<'a> Fn(&'a G, &V) -> Vec<&'a V> + 't>;
+type Check<'t, G, V> = Box<dyn Fn(&G, &V) -> bool + 't>;
+type Edge<'t, G, V, T> = Box<dyn Fn(&G, &V, &V) -> T + 't>;
+
+/// A graph that behaves like the one it wraps, except for whichever of its
+/// methods are replaced. Replacements are handed the wrapped graph along with
+/// the arguments, so they can still ask it.
+pub struct Tweaked<'t, G, V = Cell> {
+	pub graph: &'t G,
+	neighbors: Option<Neighbors<'t, G, V>>,
+	transversable: Option<Edge<'t, G, V, bool>>,
+	has_vertex: Option<Check<'t, G, V>>,
+	heuristic: Option<Edge<'t, G, V, f64>>,
+	travel_cost: Option<Edge<'t, G, V, f64>>,
+}
+
+impl<'t, G, V> Tweaked<'t, G, V> {
+	pub fn new(graph: &'t G) -> Self {
+		Self {
+			graph,
+			neighbors: None,
+			transversable: None,
+			has_vertex: None,
+			heuristic: None,
+			travel_cost: None,
+		}
+	}
+
+	pub fn neighbors(mut self, neighbors: impl for<'a> Fn(&'a G, &V) -> Vec<&'a V> + 't) -> Self {
+		self.neighbors = Some(Box::new(neighbors));
+		self
+	}
+
+	pub fn transversable(mut self, transversable: impl Fn(&G, &V, &V) -> bool + 't) -> Self {
+		self.transversable = Some(Box::new(transversable));
+		self
+	}
+
+	pub fn has_vertex(mut self, has_vertex: impl Fn(&G, &V) -> bool + 't) -> Self {
+		self.has_vertex = Some(Box::new(has_vertex));
+		self
+	}
+
+	pub fn heuristic(mut self, heuristic: impl Fn(&G, &V, &V) -> f64 + 't) -> Self {
+		self.heuristic = Some(Box::new(heuristic));
+		self
+	}
+
+	pub fn travel_cost(mut self, travel_cost: impl Fn(&G, &V, &V) -> f64 + 't) -> Self {
+		self.travel_cost = Some(Box::new(travel_cost));
+		self
+	}
+}
+
+impl<G, V> Graph2D<V> for Tweaked<'_, G, V>
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	fn neighbors(&self, vertex: &V) -> Vec<&V> {
+		match &self.neighbors {
+			Some(neighbors) => neighbors(self.graph, vertex),
+			None => self.graph.neighbors(vertex),
+		}
+	}
+
+	fn path_is_transversable(&self, vertex: &V, other: &V) -> bool {
+		match &self.transversable {
+			Some(transversable) => transversable(self.graph, vertex, other),
+			None => self.graph.path_is_transversable(vertex, other),
+		}
+	}
+
+	fn has_vertex(&self, vertex: &V) -> bool {
+		match &self.has_vertex {
+			Some(has_vertex) => has_vertex(self.graph, vertex),
+			None => self.graph.has_vertex(vertex),
+		}
+	}
+
+	fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+		match &self.heuristic {
+			Some(heuristic) => heuristic(self.graph, vertex, other),
+			None => self.graph.heuristic(vertex, other),
+		}
+	}
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		match &self.travel_cost {
+			Some(travel_cost) => travel_cost(self.graph, vertex, other),
+			None => self.graph.travel_cost(vertex, other),
+		}
+	}
+
+	fn scale(&self) -> (f64, f64) {
+		self.graph.scale()
+	}
+}
+
+/// A few vertices joined by two-way edges, given by the indices of their
+/// ends, each costing the straight line between them.
+///
+/// Vertices are told apart by where they are stored rather than by equality,
+/// so that vertices comparing equal can be tried out.
+pub struct Network<V> {
+	vertices: Vec<V>,
+	edges: Vec<(usize, usize)>,
+}
+
+impl<V> Network<V> {
+	pub fn new(vertices: Vec<V>, edges: Vec<(usize, usize)>) -> Self {
+		Self { vertices, edges }
+	}
+
+	/// Joins every vertex to every other one.
+	pub fn complete(vertices: Vec<V>) -> Self {
+		let count = vertices.len();
+		let edges = (0..count).flat_map(|from| (from + 1..count).map(move |to| (from, to))).collect();
+		Self::new(vertices, edges)
+	}
+
+	pub fn at(&self, index: usize) -> &V {
+		&self.vertices[index]
+	}
+}
+
+impl<V: Vertex2D + PartialEq> Graph2D<V> for Network<V> {
+	fn neighbors(&self, vertex: &V) -> Vec<&V> {
+		let Some(index) = self.vertices.iter().position(|other| std::ptr::eq(other, vertex)) else {
+			return Vec::new();
+		};
+		let ends = self.edges.iter().filter_map(|&(from, to)| match (from == index, to == index) {
+			(true, _) => Some(to),
+			(_, true) => Some(from),
+			_ => None,
+		});
+		ends.map(|end| self.at(end)).collect()
+	}
+
+	fn path_is_transversable(&self, _: &V, _: &V) -> bool {
+		true
+	}
+
+	fn has_vertex(&self, vertex: &V) -> bool {
+		self.vertices.contains(vertex)
+	}
+
+	fn heuristic(&self, vertex: &V, other: &V) -> f64 {
+		vertex.euclidean_distance(other)
+	}
+
+	fn travel_cost(&self, vertex: &V, other: &V) -> f64 {
+		vertex.euclidean_distance(other)
+	}
+}
@@ -0,0 +1,154 @@
+// Not every test crate uses every helper
+#![allow(dead_code)]
+
+pub mod graphs;
+pub mod test_grids;
+
+use a_star::grid::{grid_neighbors_8, step_is_clear_with_policy, CornerPolicy};
+use a_star::{Graph2D, Vertex2D};
+
+/// A cell of a [`Grid`], identified by its coordinates.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+	pub x: usize,
+	pub y: usize,
+	pub is_wall: bool,
+}
+
+impl Vertex2D for Cell {
+	#[allow(clippy::cast_precision_loss)]
+	fn coords(&self) -> (f64, f64) {
+		(self.x as f64, self.y as f64)
+	}
+}
+
+/// An 8-connected grid in which diagonal moves may not cut the corner of a
+/// wall. Moving costs the euclidean distance between cells, and the chebyshev
+/// distance is used as the heuristic.
+pub struct Grid {
+	cells: Vec<Vec<Cell>>,
+}
+
+impl Grid {
+	/// Builds a grid from its rows, in which `#` is a wall and anything else
+	/// is free to walk on.
+	pub fn parse(rows: &[&str]) -> Self {
+		let cells = rows
+			.iter()
+			.enumerate()
+			.map(|(y, row)| {
+				row.chars()
+					.enumerate()
+					.map(|(x, c)| Cell {
+						x,
+						y,
+						is_wall: c == '#',
+					})
+					.collect()
+			})
+			.collect();
+		Self { cells }
+	}
+
+	/// Builds a grid in which the cells `is_wall` tells apart are walls.
+	pub fn from_fn<F>(width: usize, height: usize, is_wall: F) -> Self
+	where
+		F: Fn(usize, usize) -> bool,
+	{
+		let cells = (0..height)
+			.map(|y| {
+				(0..width)
+					.map(|x| Cell {
+						x,
+						y,
+						is_wall: is_wall(x, y),
+					})
+					.collect()
+			})
+			.collect();
+		Self { cells }
+	}
+
+	/// Builds a grid without any walls.
+	pub fn open(width: usize, height: usize) -> Self {
+		let row = ".".repeat(width);
+		Self::parse(&vec![row.as_str(); height])
+	}
+
+	pub fn width(&self) -> usize {
+		self.cells.first().map_or(0, Vec::len)
+	}
+
+	pub fn height(&self) -> usize {
+		self.cells.len()
+	}
+
+	pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+		self.cells.get(y).and_then(|row| row.get(x))
+	}
+
+	pub fn at(&self, x: usize, y: usize) -> &Cell {
+		self.get(x, y).expect("cell out of bounds")
+	}
+
+	/// Iterates over every cell, row by row.
+	pub fn cells(&self) -> impl Iterator<Item = &Cell> {
+		self.cells.iter().flatten()
+	}
+
+	pub fn set_wall(&mut self, x: usize, y: usize, is_wall: bool) {
+		self.cells[y][x].is_wall = is_wall;
+	}
+
+	fn is_free(&self, x: usize, y: usize) -> bool {
+		self.get(x, y).is_some_and(|cell| !cell.is_wall)
+	}
+}
+
+impl Graph2D<Cell> for Grid {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		let neighbors = grid_neighbors_8((cell.x, cell.y), self.width(), self.height());
+		neighbors.into_iter().map(|(x, y)| self.at(x, y)).collect()
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		let is_free = |x, y| self.is_free(x, y);
+		!from.is_wall
+			&& !to.is_wall
+			&& step_is_clear_with_policy((from.x, from.y), (to.x, to.y), is_free, CornerPolicy::DisallowEither)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.get(cell.x, cell.y) == Some(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		cell.chebyshev_distance(other)
+	}
+
+	fn travel_cost(&self, cell: &Cell, other: &Cell) -> f64 {
+		cell.euclidean_distance(other)
+	}
+}
+
+/// Sums the travel cost of every step of a path.
+pub fn path_cost<G, V>(map: &G, path: &[&V]) -> f64
+where
+	G: Graph2D<V>,
+	V: Vertex2D,
+{
+	path.windows(2)
+		.map(|step| map.travel_cost(step[0], step[1]))
+		.sum()
+}
+
+/// Whether every step of a path is a transversable move between neighbors.
+pub fn is_valid_path<G, V>(map: &G, path: &[&V]) -> bool
+where
+	G: Graph2D<V>,
+	V: Vertex2D + PartialEq,
+{
+	path.windows(2).all(|step| {
+		map.neighbors(step[0]).contains(&step[1]) && map.path_is_transversable(step[0], step[1])
+	})
+}
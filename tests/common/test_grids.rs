@@ -0,0 +1,65 @@
+//! Grids shared by the tests and the benches, so that both work on the very
+//! same obstacles, and benches stay comparable across runs.
+
+use super::Grid;
+
+/// How wide every grid here is.
+pub const WIDTH: usize = 50;
+/// How tall every grid here is.
+pub const HEIGHT: usize = 20;
+
+/// Walls sticking out of the top and the bottom, one after the other, so that
+/// a path has to weave in between them.
+pub fn spikes() -> Grid {
+	Grid::from_fn(WIDTH, HEIGHT, |x, y| (x % 10 == 3 && y > 5) || (x % 10 == 8 && y < 15))
+}
+
+/// A diagonal staircase of walls, in the way of anything heading across it.
+pub fn hill() -> Grid {
+	Grid::from_fn(WIDTH, HEIGHT, |x, y| x < 48 && x / 5 == y && y < 15)
+}
+
+/// Walls shaped like an L, with a pocket that a greedy search walks right
+/// into.
+pub fn l_shape() -> Grid {
+	Grid::from_fn(WIDTH, HEIGHT, |x, y| {
+		(x == 5 && (3..=5).contains(&y))
+			|| (x == 30 && (5..=10).contains(&y))
+			|| (x == 35 && (3..=10).contains(&y))
+			|| (y == 3 && (5..=35).contains(&x))
+			|| (y == 5 && (5..=30).contains(&x))
+			|| (y == 10 && (30..=35).contains(&x))
+	})
+}
+
+/// Walls scattered at random, each cell being one with a chance of `density`,
+/// the same ones for the same seed on every run.
+///
+/// The corners are always left free, so there is somewhere to start from and
+/// head to, though not necessarily a path in between.
+pub fn random(seed: u64, density: f64) -> Grid {
+	let mut rng = SplitMix(seed);
+	let walls: Vec<bool> = (0..WIDTH * HEIGHT).map(|_| rng.next_unit() < density).collect();
+	let is_corner = |x, y| (x == 0 || x == WIDTH - 1) && (y == 0 || y == HEIGHT - 1);
+	Grid::from_fn(WIDTH, HEIGHT, |x, y| walls[y * WIDTH + x] && !is_corner(x, y))
+}
+
+/// The SplitMix64 generator, which is plenty random for laying walls out,
+/// and small enough not to pull a crate in for it.
+struct SplitMix(u64);
+
+impl SplitMix {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+
+	/// Returns a number between `0` inclusive and `1` exclusive.
+	#[allow(clippy::cast_precision_loss)]
+	fn next_unit(&mut self) -> f64 {
+		(self.next() >> 11) as f64 / (1_u64 << 53) as f64
+	}
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use a_star::cost::{a_star_with_elevation, CostStack, ElevationGraph, RoadNetwork, TrafficGraph};
+use a_star::{a_star, Graph2D};
+use common::{is_valid_path, path_cost, Cell, Grid};
+
+#[test]
+fn stacked_modifiers_apply_in_order_and_steer_the_path() {
+	let grid = Grid::open(7, 5);
+	let avoid = [grid.at(3, 0), grid.at(3, 1)];
+	// The top row is mud, thrice as slow to walk into
+	let mud = |_: &Cell, to: &Cell, cost: f64| if to.y == 0 { cost * 3.0 } else { cost };
+	let penalty = |_: &Cell, to: &Cell, cost: f64| if avoid.contains(&to) { cost + 10.0 } else { cost };
+	let stack = CostStack::new(&grid).with(mud).with(penalty);
+	assert_eq!(stack.travel_cost(grid.at(2, 0), grid.at(3, 0)), 13.0);
+	assert_eq!(stack.travel_cost(grid.at(2, 1), grid.at(3, 1)), 11.0);
+	assert_eq!(stack.travel_cost(grid.at(2, 2), grid.at(3, 2)), 1.0);
+	let (start, goal) = (grid.at(0, 0), grid.at(6, 0));
+	let path = a_star(&stack, start, goal).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert!(path.iter().all(|cell| !avoid.contains(cell)));
+	assert!(path[1..path.len() - 1].iter().all(|cell| cell.y > 0));
+	assert!(path_cost(&stack, &path) < path_cost(&stack, &a_star(&grid, start, goal).unwrap()));
+}
+
+#[test]
+fn roads_are_preferred_over_slightly_shorter_ways_off_them() {
+	let grid = Grid::open(9, 3);
+	// A road runs along the top row, with ramps down at both ends
+	let road = |cell: &Cell| cell.y == 0 || cell.x == 0 || cell.x == 8;
+	let roads = RoadNetwork::new(&grid, road, 0.5);
+	let (start, goal) = (grid.at(0, 2), grid.at(8, 2));
+	assert_eq!(path_cost(&grid, &a_star(&grid, start, goal).unwrap()), 8.0);
+	let path = a_star(&roads, start, goal).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert!(path.iter().all(|cell| road(cell)));
+	assert!(path_cost(&grid, &path) > 8.0);
+	assert!(path_cost(&roads, &path) < 8.0);
+}
+
+#[test]
+fn busy_routes_are_avoided_by_later_searches() {
+	let grid = Grid::parse(&[".......", ".#####.", "......."]);
+	let (start, goal) = (grid.at(0, 1), grid.at(6, 1));
+	let mut traffic = TrafficGraph::new(&grid, 0.5);
+	let first = a_star(&grid, start, goal).unwrap();
+	for _ in 0..3 {
+		traffic.record_path(&first);
+	}
+	assert_eq!(traffic.traffic(first[1]), 3.0);
+	let second = a_star(&traffic, start, goal).unwrap();
+	assert!(is_valid_path(&grid, &second));
+	assert!(second[1..second.len() - 1].iter().all(|cell| !first.contains(cell)));
+	// Once the traffic dies down, the first route is back to being the best
+	traffic.decay(0.0);
+	assert_eq!(a_star(&traffic, start, goal).unwrap(), first);
+}
+
+#[test]
+fn flat_detours_are_preferred_over_steep_climbs() {
+	let grid = Grid::open(7, 4);
+	// A hill rises in the middle of the top three rows, leaving the bottom
+	// one flat
+	let height = |cell: &Cell| if cell.y < 3 { 3.0 - (cell.x as f64 - 3.0).abs() } else { 0.0 };
+	let terrain = ElevationGraph::new(&grid, height, 2.0);
+	assert_eq!(terrain.travel_cost(grid.at(2, 0), grid.at(3, 0)), 3.0);
+	assert_eq!(terrain.travel_cost(grid.at(3, 0), grid.at(4, 0)), 1.0);
+	let (start, goal) = (grid.at(0, 0), grid.at(6, 0));
+	assert!(a_star(&grid, start, goal).unwrap().iter().all(|cell| cell.y == 0));
+	let path = a_star(&terrain, start, goal).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert!(path.iter().any(|cell| cell.y == 3));
+	assert!(path_cost(&grid, &path) > 6.0);
+}
+
+#[test]
+fn elevation_gain_sums_the_climbs_along_the_path() {
+	let grid = Grid::open(7, 3);
+	// A ridge runs down the middle, gentler towards the bottom
+	let height = |cell: &Cell| if cell.x == 3 { 4.0 - cell.y as f64 } else { (cell.y as f64) / 2.0 };
+	let terrain = ElevationGraph::new(&grid, height, 1.0);
+	let (start, goal) = (grid.at(0, 0), grid.at(6, 0));
+	let (path, gain) = a_star_with_elevation(&grid, start, goal, height, 1.0).unwrap();
+	assert_eq!(Some(&path), a_star(&terrain, start, goal).as_ref());
+	assert_ne!(Some(&path), a_star(&grid, start, goal).as_ref());
+	let climbs = path.windows(2).map(|step| height(step[1]) - height(step[0]));
+	assert_eq!(gain, climbs.filter(|&climb| climb > 0.0).sum::<f64>());
+	assert!(path.iter().any(|cell| (cell.x, cell.y) == (3, 2)));
+	assert!(gain > 0.0);
+}
@@ -0,0 +1,25 @@
+mod common;
+
+use a_star::a_star_with_tree;
+use a_star::dot::export_search_dot;
+use common::Grid;
+
+#[test]
+fn search_tree_is_exported_with_the_path_highlighted() {
+	let grid = Grid::parse(&["...", "#.#"]);
+	let (path, tree) = a_star_with_tree(&grid, grid.at(0, 0), grid.at(2, 0)).unwrap();
+	assert_eq!(tree.len(), 3);
+	assert_eq!(tree[grid.at(1, 1)], grid.at(1, 0));
+	let expected = [
+		"digraph search {",
+		"\t\"0,0\" [label=\"(0, 0)\" color=red penwidth=2];",
+		"\t\"1,0\" [label=\"(1, 0)\" color=red penwidth=2];",
+		"\t\"1,1\" [label=\"(1, 1)\"];",
+		"\t\"2,0\" [label=\"(2, 0)\" color=red penwidth=2];",
+		"\t\"0,0\" -> \"1,0\" [color=red penwidth=2];",
+		"\t\"1,0\" -> \"1,1\";",
+		"\t\"1,0\" -> \"2,0\" [color=red penwidth=2];",
+		"}",
+	];
+	assert_eq!(export_search_dot(&tree, &path), expected.join("\n") + "\n");
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use a_star::dynamic::{a_star_dyn, Erased, Graph2DDyn};
+use a_star::motion::TurnPenalty;
+use a_star::roadmap::RoadmapGraph;
+use a_star::{a_star, Vertex2D};
+use common::{Cell, Grid};
+
+/// A vertex of a type no graph here is made of.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Point(i32, i32);
+
+impl Vertex2D for Point {
+	fn coords(&self) -> (f64, f64) {
+		(f64::from(self.0), f64::from(self.1))
+	}
+}
+
+fn cell(x: usize, y: usize) -> Cell {
+	Cell { x, y, is_wall: false }
+}
+
+#[test]
+fn graphs_picked_at_runtime_are_searched_alike() {
+	let grid = Grid::parse(&["....", ".##.", "...."]);
+	let (start, goal) = (cell(0, 0), cell(3, 2));
+	let expected = a_star(&grid, grid.at(0, 0), grid.at(3, 2)).unwrap();
+	let expected: Vec<_> = expected.iter().map(|cell| cell.coords()).collect();
+	let points = vec![cell(0, 0), cell(1, 0), cell(2, 1), cell(3, 2), cell(0, 2)];
+	let graphs: Vec<Box<dyn Graph2DDyn>> = vec![
+		Box::new(Erased::new(grid)),
+		Box::new(Erased::new(RoadmapGraph::new(points, 1.5))),
+	];
+	let paths: Vec<_> = graphs
+		.iter()
+		.map(|graph| {
+			let path = a_star_dyn(graph.as_ref(), &start, &goal).unwrap();
+			path.iter().map(|vertex| vertex.coords()).collect::<Vec<_>>()
+		})
+		.collect();
+	assert_eq!(paths[0], expected);
+	assert_eq!(paths[1], vec![(0.0, 0.0), (1.0, 0.0), (2.0, 1.0), (3.0, 2.0)]);
+	// Vertices of another type are not part of either graph
+	for graph in &graphs {
+		assert!(!graph.has_vertex(&Point(0, 0)));
+		assert!(a_star_dyn(graph.as_ref(), &Point(0, 0), &goal).is_none());
+	}
+}
+
+#[test]
+fn runtime_graphs_keep_their_context_costs_and_predecessors() {
+	let smooth = TurnPenalty::new(Grid::parse(&["......", ".####.", "......", "......"]), 2.0);
+	let coords = |path: &[&Cell]| path.iter().map(|cell| cell.coords()).collect::<Vec<_>>();
+	let (start, goal) = (smooth.at(0, 0), smooth.at(5, 3));
+	let expected = coords(&a_star(&smooth, start, goal).unwrap());
+	assert_ne!(expected, coords(&a_star(&*smooth, start, goal).unwrap()));
+	let erased = Erased::new(smooth);
+	let path = a_star_dyn(&erased, &cell(0, 0), &cell(5, 3)).unwrap();
+	assert_eq!(path.iter().map(|vertex| vertex.coords()).collect::<Vec<_>>(), expected);
+	assert_eq!(erased.predecessors(&cell(1, 0)).len(), 5);
+	assert_eq!(erased.scale(), (1.0, 1.0));
+}
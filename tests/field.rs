@@ -0,0 +1,52 @@
+mod common;
+
+use a_star::field::{cost_field, DynamicCostField};
+use a_star::Graph2D;
+use common::graphs::Tweaked;
+use common::{Cell, Grid};
+
+/// Asserts that a field holds the same costs as a fresh one, computed from
+/// scratch with the given cells blocked.
+fn assert_matches_fresh(field: &DynamicCostField<Grid, Cell>, grid: &Grid, blocked: &[(usize, usize)]) {
+	// The cells can't be moved into or out of, without making walls of them,
+	// whose corners couldn't be cut
+	let is_blocked = |cell: &Cell| blocked.contains(&(cell.x, cell.y));
+	let map = Tweaked::new(grid).transversable(|grid, from, to| {
+		!is_blocked(from) && !is_blocked(to) && grid.path_is_transversable(from, to)
+	});
+	let fresh = cost_field(&map, grid.at(0, 0));
+	for y in 0..grid.height() {
+		for x in 0..grid.width() {
+			let expected = fresh.get(grid.at(x, y)).copied().unwrap_or(f64::INFINITY);
+			let cost = field.cost(grid.at(x, y));
+			assert!(cost == expected || (cost - expected).abs() < 1e-9, "{:?}: {} != {}", (x, y), cost, expected);
+		}
+	}
+}
+
+#[test]
+fn blocking_repairs_only_the_affected_costs() {
+	let grid = Grid::parse(&["............"; 12]);
+	let mut field = DynamicCostField::new(&grid, grid.at(0, 0));
+	assert_matches_fresh(&field, &grid, &[]);
+	let built = field.updates();
+	assert_eq!(built, 12 * 12);
+	// Out of the way, so hardly anything goes through it
+	field.block(grid.at(11, 10));
+	assert_matches_fresh(&field, &grid, &[(11, 10)]);
+	assert!(field.updates() - built < built / 10);
+	// A wall most of the way across
+	let mut blocked = vec![(11, 10)];
+	for x in 0..10 {
+		field.block(grid.at(x, 5));
+		blocked.push((x, 5));
+		assert_matches_fresh(&field, &grid, &blocked);
+	}
+	for &(x, y) in &blocked {
+		field.unblock(grid.at(x, y));
+	}
+	assert_matches_fresh(&field, &grid, &[]);
+	field.block(grid.at(0, 0));
+	assert!(field.cost(grid.at(0, 0)).is_infinite());
+	assert!(field.cost(grid.at(1, 1)).is_infinite());
+}
@@ -0,0 +1,190 @@
+mod common;
+
+use a_star::a_star;
+use a_star::flood::{
+	all_pairs, flee_path, influence_map, multi_source_voronoi, nearest_reachable, path_clearance, path_fits,
+	reachable_hull, reachable_medoid, reachable_within, Accumulate,
+};
+use common::{path_cost, Grid};
+
+#[test]
+fn reachable_within_stops_at_the_cost_limit() {
+	let grid = Grid::open(5, 5);
+	let reachable = reachable_within(&grid, grid.at(2, 2), 1.5);
+	// The 8 surrounding cells and the start itself
+	assert_eq!(reachable.len(), 9);
+	assert_eq!(reachable[grid.at(2, 2)], 0.0);
+	assert_eq!(reachable[grid.at(3, 2)], 1.0);
+	assert!((reachable[grid.at(3, 3)] - 2f64.sqrt()).abs() < 1e-9);
+	assert!(reachable_within(&grid, grid.at(2, 2), -1.0).is_empty());
+}
+
+#[test]
+fn nearest_reachable_cells_are_the_cheapest_ones() {
+	let grid = Grid::parse(&[".....", ".##..", ".#...", "....."]);
+	let start = grid.at(0, 0);
+	let everything = reachable_within(&grid, start, f64::INFINITY);
+	let nearest = nearest_reachable(&grid, start, 6);
+	assert_eq!(nearest.len(), 6);
+	assert!(nearest.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+	let furthest = nearest[5].1;
+	for (cell, cost) in &everything {
+		match nearest.iter().find(|(other, _)| other == cell) {
+			Some(&(_, nearest_cost)) => assert!((nearest_cost - cost).abs() < 1e-9),
+			None => assert!(*cost >= furthest),
+		}
+	}
+	assert_eq!(nearest_reachable(&grid, start, 100).len(), everything.len());
+}
+
+#[test]
+fn medoid_of_a_symmetric_region_is_its_center() {
+	let grid = Grid::open(7, 7);
+	// The region is a diamond-ish blob centered around the start
+	let medoid = reachable_medoid(&grid, grid.at(3, 3), 2.0).unwrap();
+	assert_eq!(medoid, grid.at(3, 3));
+	let grid = Grid::parse(&["#.#", "...", "#.#"]);
+	let medoid = reachable_medoid(&grid, grid.at(1, 0), 10.0).unwrap();
+	assert_eq!(medoid, grid.at(1, 1));
+}
+
+#[test]
+fn equidistant_cells_are_influenced_by_both_sources() {
+	let grid = Grid::open(7, 1);
+	let sources = [(grid.at(0, 0), 1.0), (grid.at(6, 0), 2.0)];
+	let total = influence_map(&grid, &sources, 4.0, Accumulate::Sum);
+	assert_eq!(total[grid.at(3, 0)], 1.0 * 0.25 + 2.0 * 0.25);
+	// Only the first source reaches this far west
+	assert_eq!(total[grid.at(1, 0)], 1.0 * 0.75);
+	let strongest = influence_map(&grid, &sources, 4.0, Accumulate::Maximum);
+	assert_eq!(strongest[grid.at(3, 0)], 2.0 * 0.25);
+	assert_eq!(strongest[grid.at(5, 0)], 2.0 * 0.75);
+	assert_eq!(strongest.len(), 7);
+}
+
+#[test]
+fn influence_falls_off_with_distance() {
+	let grid = Grid::open(9, 3);
+	let sources = [(grid.at(0, 1), 3.0), (grid.at(0, 1), 1.0)];
+	for &accumulate in &[Accumulate::Sum, Accumulate::Maximum] {
+		let influence = influence_map(&grid, &sources, 6.0, accumulate);
+		let row: Vec<_> = (0..7).map(|x| influence[grid.at(x, 1)]).collect();
+		assert!(row.windows(2).all(|pair| pair[1] < pair[0]), "{:?}", row);
+		assert_eq!(row[6], 0.0);
+		assert!(!influence.contains_key(grid.at(7, 1)));
+	}
+}
+
+#[test]
+fn influence_without_any_reach_stays_at_the_sources() {
+	let grid = Grid::open(5, 1);
+	let sources = [(grid.at(0, 0), 3.0), (grid.at(4, 0), 1.0), (grid.at(4, 0), 2.0)];
+	let total = influence_map(&grid, &sources, 0.0, Accumulate::Sum);
+	assert_eq!(total.len(), 2);
+	assert_eq!((total[grid.at(0, 0)], total[grid.at(4, 0)]), (3.0, 3.0));
+	let strongest = influence_map(&grid, &sources, 0.0, Accumulate::Maximum);
+	assert_eq!((strongest[grid.at(0, 0)], strongest[grid.at(4, 0)]), (3.0, 2.0));
+	assert!(influence_map(&grid, &sources, -1.0, Accumulate::Sum).is_empty());
+}
+
+#[test]
+fn all_pairs_costs_match_individual_searches() {
+	let grid = Grid::parse(&["..#", "..."]);
+	let nodes: Vec<_> = (0..2)
+		.flat_map(|y| (0..3).map(move |x| (x, y)))
+		.map(|(x, y)| grid.at(x, y).clone())
+		.collect();
+	let costs = all_pairs(&grid, &nodes);
+	for from in &nodes {
+		for to in &nodes {
+			let path = a_star(&grid, grid.at(from.x, from.y), grid.at(to.x, to.y));
+			match (costs.query(from, to), path) {
+				(Some(cost), Some(path)) => assert!((cost - path_cost(&grid, &path)).abs() < 1e-9),
+				(None, None) => {}
+				(cost, path) => panic!("{:?} to {:?}: {:?} but {:?}", from, to, cost, path),
+			}
+		}
+	}
+	assert_eq!(costs.query(&nodes[0], &nodes[5]), Some(1.0 + 2f64.sqrt()));
+	assert_eq!(costs.query(&nodes[0], &nodes[2]), None);
+}
+
+#[test]
+fn flee_path_runs_to_the_furthest_cell_from_the_threat() {
+	let grid = Grid::parse(&["......", "#####.", "......"]);
+	let (threat, start) = (grid.at(0, 0), grid.at(1, 0));
+	// Down the whole snake, to its other end
+	let path = flee_path(&grid, start, threat, 20).unwrap();
+	assert_eq!(path[0], start);
+	assert_eq!(path[path.len() - 1], grid.at(0, 2));
+	// As far down as it can go in a few steps
+	let path = flee_path(&grid, start, threat, 3).unwrap();
+	assert_eq!(path, vec![start, grid.at(2, 0), grid.at(3, 0), grid.at(4, 0)]);
+	let walled = Grid::parse(&[".#."]);
+	assert!(flee_path(&walled, walled.at(0, 0), walled.at(2, 0), 5).is_none());
+}
+
+#[test]
+fn reachable_hull_outlines_the_region_around_the_start() {
+	let grid = Grid::open(9, 9);
+	let hull = reachable_hull(&grid, grid.at(4, 4), 3.0);
+	// Three cells straight out, or two diagonally
+	let corners = [(1, 4), (2, 2), (4, 1), (6, 2), (7, 4), (6, 6), (4, 7), (2, 6)];
+	let corners: Vec<_> = corners.iter().map(|&(x, y)| (f64::from(x), f64::from(y))).collect();
+	assert_eq!(hull, corners);
+	let corridor = Grid::parse(&["#####", ".....", "#####"]);
+	let hull = reachable_hull(&corridor, corridor.at(2, 1), 10.0);
+	assert_eq!(hull, vec![(0.0, 1.0), (4.0, 1.0)]);
+	assert_eq!(reachable_hull(&grid, grid.at(4, 4), 0.5), vec![(4.0, 4.0)]);
+}
+
+#[test]
+fn narrow_paths_only_fit_small_agents() {
+	let grid = Grid::parse(&[
+		"##########",
+		"#........#",
+		"#........#",
+		"#.....####",
+		"#........#",
+		"#........#",
+		"##########",
+	]);
+	let (start, goal) = (grid.at(1, 3), grid.at(8, 2));
+	let path = a_star(&grid, start, goal).unwrap();
+	let clearance = path_clearance(&grid, &path);
+	assert_eq!(clearance.len(), path.len());
+	assert_eq!(clearance[0], 1.0);
+	assert!(path_fits(&grid, &path, 1.0));
+	assert!(!path_fits(&grid, &path, 1.5));
+	let wide = [grid.at(2, 3), grid.at(3, 3)];
+	assert_eq!(path_clearance(&grid, &wide), vec![2.0, 3.0]);
+	assert!(path_fits(&grid, &wide, 2.0));
+}
+
+#[test]
+fn the_border_of_the_graph_counts_as_an_obstacle() {
+	let grid = Grid::open(5, 5);
+	let path = [grid.at(0, 0), grid.at(1, 1), grid.at(2, 2)];
+	assert_eq!(path_clearance(&grid, &path), vec![1.0, 2.0, 3.0]);
+	// Just like a wall all around it
+	let walled = Grid::parse(&["#######", "#.....#", "#.....#", "#.....#", "#.....#", "#.....#", "#######"]);
+	let path = [walled.at(1, 1), walled.at(2, 2), walled.at(3, 3)];
+	assert_eq!(path_clearance(&walled, &path), vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn voronoi_regions_meet_halfway_between_the_sources() {
+	let grid = Grid::parse(&[".........", ".........", ".........", "########."]);
+	let sources = [grid.at(0, 1).clone(), grid.at(6, 1).clone()];
+	let regions = multi_source_voronoi(&grid, &sources);
+	assert_eq!(regions.len(), 28);
+	for y in 0..3 {
+		for x in 0..9 {
+			// The middle column is as far from both, so it goes to the first
+			let expected = if x <= 3 { 0 } else { 1 };
+			assert_eq!(regions[grid.at(x, y)], expected, "at ({}, {})", x, y);
+		}
+	}
+	assert_eq!(regions[grid.at(8, 3)], 1);
+	assert!(!regions.contains_key(grid.at(0, 3)));
+}
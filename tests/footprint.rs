@@ -0,0 +1,31 @@
+mod common;
+
+use a_star::a_star;
+use a_star::footprint::FootprintGraph;
+use common::{is_valid_path, path_cost, Cell, Grid};
+
+#[test]
+fn bulky_agents_go_around_narrow_gaps() {
+	let grid = Grid::parse(&[
+		"..........",
+		"..........",
+		"..........",
+		"##.####..#",
+		"..........",
+		"..........",
+		"..........",
+	]);
+	let (start, goal) = (grid.at(1, 0), grid.at(1, 5));
+	let slim = FootprintGraph::new(&grid, (1, 1));
+	let small = a_star(&slim, start, goal).unwrap();
+	assert!(small.iter().any(|cell| (cell.x, cell.y) == (2, 3)));
+	let bulky = FootprintGraph::new(&grid, (2, 2));
+	let path = a_star(&bulky, start, goal).unwrap();
+	assert!(is_valid_path(&bulky, &path));
+	assert!(path.iter().any(|cell| (cell.x, cell.y) == (7, 3)));
+	// Every cell the agent covers along the way is free
+	let covers_walls = |cell: &&Cell| (0..4).any(|i| grid.at(cell.x + i % 2, cell.y + i / 2).is_wall);
+	assert!(!path.iter().any(covers_walls));
+	assert!(path_cost(&grid, &path) > path_cost(&grid, &small));
+	assert_eq!(a_star(&bulky, start, grid.at(1, 6)), None);
+}
@@ -0,0 +1,79 @@
+use a_star::grid::{
+	grid_neighbors_4, grid_neighbors_8, offset, step_is_clear, validate_path, CornerPolicy, GridFrame,
+};
+
+#[test]
+fn neighbors_8_counts_at_corner_edge_and_interior() {
+	assert_eq!(grid_neighbors_8((0, 0), 5, 5).len(), 3);
+	assert_eq!(grid_neighbors_8((4, 4), 5, 5).len(), 3);
+	assert_eq!(grid_neighbors_8((2, 0), 5, 5).len(), 5);
+	assert_eq!(grid_neighbors_8((0, 2), 5, 5).len(), 5);
+	assert_eq!(grid_neighbors_8((2, 2), 5, 5).len(), 8);
+}
+
+#[test]
+fn neighbors_4_counts_at_corner_edge_and_interior() {
+	assert_eq!(grid_neighbors_4((0, 0), 5, 5).len(), 2);
+	assert_eq!(grid_neighbors_4((2, 4), 5, 5).len(), 3);
+	assert_eq!(grid_neighbors_4((2, 2), 5, 5), vec![(2, 1), (1, 2), (3, 2), (2, 3)]);
+}
+
+#[test]
+fn neighbors_never_include_the_cell_itself() {
+	assert!(!grid_neighbors_8((1, 1), 3, 3).contains(&(1, 1)));
+	assert!(grid_neighbors_8((0, 0), 1, 1).is_empty());
+}
+
+#[test]
+fn diagonal_steps_need_a_free_corner() {
+	let walls = [(1, 0), (0, 1)];
+	let is_free = |x, y| !walls.contains(&(x, y));
+	assert!(!step_is_clear((0, 0), (1, 1), is_free));
+	assert!(step_is_clear((1, 0), (0, 1), |_, _| true));
+	assert!(step_is_clear((2, 1), (1, 2), is_free));
+	assert!(step_is_clear((0, 0), (1, 0), is_free));
+	assert!(!step_is_clear((0, 0), (2, 0), is_free));
+}
+
+#[test]
+fn diagonal_steps_near_the_end_of_the_address_space() {
+	const MAX: usize = usize::MAX;
+	// A tiny virtual 2x2 grid in the far corner of the address space, in
+	// which only (MAX - 1, MAX) is a wall
+	let is_free = |x: usize, y: usize| x >= MAX - 1 && y >= MAX - 1 && (x, y) != (MAX - 1, MAX);
+	assert!(step_is_clear((MAX - 1, MAX - 1), (MAX, MAX), is_free));
+	let blocked = |x: usize, y: usize| is_free(x, y) && (x, y) != (MAX, MAX - 1);
+	assert!(!step_is_clear((MAX - 1, MAX - 1), (MAX, MAX), blocked));
+	assert!(!step_is_clear((MAX, MAX), (MAX - 1, MAX - 1), blocked));
+	assert!(!step_is_clear((0, 0), (MAX, MAX), |_, _| true));
+	assert_eq!(offset((MAX, 0), (1, 0)), None);
+	assert_eq!(offset((MAX, 0), (-1, 0)), Some((MAX - 1, 0)));
+}
+
+#[test]
+fn validating_a_path_follows_the_corner_policy() {
+	let walls = [(1, 0), (0, 1)];
+	let is_free = |x, y| !walls.contains(&(x, y));
+	let squeezed = [(0, 0), (1, 1), (2, 2)];
+	assert!(validate_path(&squeezed, is_free, CornerPolicy::Allow));
+	assert!(!validate_path(&squeezed, is_free, CornerPolicy::DisallowBoth));
+	let grazing = [(1, 1), (2, 0)];
+	assert!(validate_path(&grazing, is_free, CornerPolicy::DisallowBoth));
+	assert!(!validate_path(&grazing, is_free, CornerPolicy::DisallowEither));
+	assert!(!validate_path(&[(0, 0), (1, 0)], is_free, CornerPolicy::Allow));
+}
+
+#[test]
+fn cell_centers_round_trip_through_the_world() {
+	let frame = GridFrame::new((-10.0, 4.0), 0.5, 6, 3);
+	for y in 0..3 {
+		for x in 0..6 {
+			assert_eq!(frame.to_cell(frame.to_world((x, y))), (x, y));
+		}
+	}
+	assert_eq!(frame.to_world((0, 0)), (-9.75, 4.25));
+	assert_eq!(frame.to_cell((-8.9, 5.0)), (2, 2));
+	// Positions off the grid clamp to its edges
+	assert_eq!(frame.to_cell((-20.0, 100.0)), (0, 2));
+	assert_eq!(frame.to_cell((100.0, f64::NAN)), (5, 0));
+}
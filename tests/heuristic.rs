@@ -0,0 +1,174 @@
+mod common;
+
+use a_star::heuristic::{
+	self, assert_heuristic_admissible, heuristic_violations, Admissible, MaxHeuristic, Violation,
+};
+use a_star::stats::a_star_with_stats;
+use a_star::{a_star, a_star_admissible, all_optimal_paths, Graph2D, Vertex2D};
+use common::graphs::Tweaked;
+use common::{is_valid_path, path_cost, Cell, Grid};
+
+/// A grid whose own heuristic wildly overestimates.
+struct Overeager(Grid);
+
+impl Graph2D<Cell> for Overeager {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		self.0.neighbors(cell)
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		self.0.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.0.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.0.heuristic(cell, other) * 10.0
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		self.0.travel_cost(from, to)
+	}
+}
+
+#[test]
+fn octile_heuristic_finds_an_optimal_path() {
+	let map = Overeager(Grid::parse(&[
+		"..........",
+		"...#......",
+		"...#..###.",
+		"...#....#.",
+		"...####.#.",
+		"........#.",
+	]));
+	let (start, goal) = (map.0.at(0, 5), map.0.at(9, 0));
+	let optimal = path_cost(&map.0, &all_optimal_paths(&map.0, start, goal, 1)[0]);
+	let path = a_star_admissible(&map, start, goal, &Admissible::octile()).unwrap();
+	assert!(is_valid_path(&map.0, &path));
+	assert!((path_cost(&map.0, &path) - optimal).abs() < 1e-9);
+}
+
+#[test]
+fn octile_matches_the_cost_of_an_unobstructed_route() {
+	let grid = Grid::open(6, 4);
+	let octile = Admissible::octile();
+	let expected = 2.0 + 3.0 * 2f64.sqrt();
+	assert!((octile.estimate(grid.at(0, 0), grid.at(5, 3)) - expected).abs() < 1e-9);
+}
+
+#[test]
+fn chebyshev_heuristic_is_consistent_with_euclidean_costs() {
+	let map = Overeager(Grid::parse(&[".....", ".#...", "...#.", "....."]));
+	let grid = &map.0;
+	let goal = grid.at(4, 0);
+	let samples: Vec<_> = (0..4)
+		.flat_map(|y| (0..5).map(move |x| (x, y)))
+		.map(|(x, y)| (grid.at(x, y), goal))
+		.collect();
+	assert_heuristic_admissible(grid, &samples);
+	// Overestimating ten times over is caught right away
+	let violations = heuristic_violations(&map, &samples);
+	assert!(!violations.is_empty());
+	assert!(violations.iter().all(|violation| match violation {
+		Violation::Inconsistent { excess, .. } => *excess > 0.0,
+		Violation::NonZeroAtGoal { .. } => false,
+	}));
+}
+
+#[test]
+#[should_panic(expected = "the heuristic overestimates")]
+fn overestimating_heuristic_fails_the_assertion() {
+	let map = Overeager(Grid::open(3, 3));
+	assert_heuristic_admissible(&map, &[(map.0.at(0, 0), map.0.at(2, 2))]);
+}
+
+/// A grid of tiles twice as tall as they are wide.
+struct Stretched(Grid);
+
+impl Graph2D<Cell> for Stretched {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		self.0.neighbors(cell)
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		self.0.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.0.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		heuristic::euclidean(self, cell, other)
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		heuristic::euclidean(self, from, to)
+	}
+
+	fn scale(&self) -> (f64, f64) {
+		(1.0, 2.0)
+	}
+}
+
+#[test]
+fn stretched_axes_are_accounted_for() {
+	let map = Stretched(Grid::parse(&["....", ".##.", "....", "...."]));
+	let grid = &map.0;
+	assert!((map.heuristic(grid.at(0, 0), grid.at(0, 3)) - 6.0).abs() < 1e-9);
+	assert!((map.heuristic(grid.at(0, 0), grid.at(3, 0)) - 3.0).abs() < 1e-9);
+	assert!((heuristic::manhattan(&map, grid.at(0, 0), grid.at(3, 3)) - 9.0).abs() < 1e-9);
+	assert!((heuristic::octile(&map, grid.at(0, 0), grid.at(1, 1)) - 5_f64.sqrt()).abs() < 1e-9);
+	assert!((heuristic::octile(&map, grid.at(0, 0), grid.at(3, 1)) - (5_f64.sqrt() + 2.0)).abs() < 1e-9);
+	let samples: Vec<_> = (0..4).map(|x| (grid.at(x, 3), grid.at(3 - x, 0))).collect();
+	assert_heuristic_admissible(&map, &samples);
+	let (start, goal) = (grid.at(1, 0), grid.at(1, 2));
+	let optimal = path_cost(&map, &a_star_admissible(&map, start, goal, &Admissible::zero()).unwrap());
+	let path = a_star(&map, start, goal).unwrap();
+	assert!((path_cost(&map, &path) - optimal).abs() < 1e-9);
+	assert!((optimal - 6.0).abs() < 1e-9);
+	for &(start, goal) in &samples {
+		let optimal = path_cost(&map, &a_star_admissible(&map, start, goal, &Admissible::zero()).unwrap());
+		assert!(heuristic::octile(&map, start, goal) <= optimal + 1e-9);
+	}
+}
+
+#[test]
+fn maximum_of_heuristics_expands_no_more_than_either() {
+	let grid = Grid::parse(&["........", "..###...", "......#.", "........", "........"]);
+	let (start, goal) = (grid.at(0, 1), grid.at(7, 4));
+	// Every step moves at most one cell along either axis, at a cost of one
+	// at least
+	let along_x = |cell: &Cell, other: &Cell| (cell.x as f64 - other.x as f64).abs();
+	let along_y = |cell: &Cell, other: &Cell| (cell.y as f64 - other.y as f64).abs();
+	let max = MaxHeuristic::new()
+		.with(Admissible::new_unchecked(along_x))
+		.with(Admissible::new_unchecked(along_y));
+	let estimate = |cell: &Cell, other: &Cell| max.estimate(cell, other);
+	let expanded = |guess: &dyn Fn(&Cell, &Cell) -> f64| {
+		let map = Tweaked::new(&grid).heuristic(|_, cell, other| guess(cell, other));
+		a_star_with_stats(&map, start, goal).1.expanded
+	};
+	assert!(expanded(&estimate) <= expanded(&along_x));
+	assert!(expanded(&estimate) <= expanded(&along_y));
+	assert!(expanded(&estimate) < expanded(&along_x).max(expanded(&along_y)));
+	let path = a_star_admissible(&grid, start, goal, &max.into_admissible()).unwrap();
+	let optimal = a_star(&grid, start, goal).unwrap();
+	assert!((path_cost(&grid, &path) - path_cost(&grid, &optimal)).abs() < 1e-9);
+}
+
+#[test]
+fn octile_distance_with_chebyshev_and_octile_costs() {
+	let grid = Grid::open(8, 8);
+	for &(x, y) in &[(0, 0), (7, 2), (3, 5), (1, 7)] {
+		let (cell, other) = (grid.at(2, 3), grid.at(x, y));
+		assert!((cell.octile_distance_with(other, 1.0, 1.0) - cell.chebyshev_distance(other)).abs() < 1e-9);
+		let octile = heuristic::octile(&grid, cell, other);
+		assert!((cell.octile_distance_with(other, std::f64::consts::SQRT_2, 1.0) - octile).abs() < 1e-9);
+	}
+	// Diagonals costlier than two axial steps are taken as two of them
+	let (cell, other) = (grid.at(0, 0), grid.at(2, 3));
+	assert!((cell.octile_distance_with(other, 5.0, 1.0) - cell.manhattan_distance(other)).abs() < 1e-9);
+}
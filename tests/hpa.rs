@@ -0,0 +1,54 @@
+mod common;
+
+use a_star::a_star;
+use a_star::hpa::HpaStar;
+use common::{is_valid_path, path_cost, Grid};
+
+/// A 64x64 grid walled into rooms, with a door in every wall.
+fn rooms() -> Grid {
+	let mut grid = Grid::open(64, 64);
+	for a in 0..64 {
+		for b in (12..64).step_by(13) {
+			if a % 13 != 6 {
+				grid.set_wall(b, a, true);
+				grid.set_wall(a, b, true);
+			}
+		}
+	}
+	grid
+}
+
+#[test]
+fn paths_cost_about_as_much_as_the_cheapest_ones() {
+	let grid = rooms();
+	let hpa = HpaStar::build(&grid, grid.cells(), 8.0);
+	for &((x, y), (goal_x, goal_y)) in &[((0, 0), (63, 63)), ((2, 60), (60, 3)), ((30, 1), (33, 62))] {
+		let (start, goal) = (grid.at(x, y), grid.at(goal_x, goal_y));
+		let cheapest = path_cost(&grid, &a_star(&grid, start, goal).unwrap());
+		let path = hpa.query(start, goal).unwrap();
+		assert!(is_valid_path(&grid, &path));
+		assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+		let cost = path_cost(&grid, &path);
+		assert!(cost <= cheapest * 1.15, "{} is too far off {}", cost, cheapest);
+	}
+}
+
+#[test]
+fn paths_within_a_cluster_are_the_cheapest_ones() {
+	let grid = rooms();
+	let hpa = HpaStar::build(&grid, grid.cells(), 8.0);
+	let (start, goal) = (grid.at(1, 1), grid.at(6, 3));
+	let path = hpa.query(start, goal).unwrap();
+	assert_eq!(path_cost(&grid, &path), path_cost(&grid, &a_star(&grid, start, goal).unwrap()));
+	assert_eq!(hpa.query(start, grid.at(12, 0)), None);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn batches_of_queries_match_serial_ones() {
+	let grid = rooms();
+	let hpa = HpaStar::build(&grid, grid.cells(), 8.0);
+	let queries: Vec<_> = (0..32).map(|i| (grid.at(i, 0), grid.at(63 - i, 63))).collect();
+	let serial: Vec<_> = queries.iter().map(|&(start, goal)| hpa.query(start, goal)).collect();
+	assert_eq!(hpa.par_query_batch(&queries), serial);
+}
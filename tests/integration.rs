@@ -0,0 +1,234 @@
+//! Smoke tests of the public API on small grids.
+
+mod common;
+
+use a_star::{a_star, a_star_indices, a_star_reversed, bidirectional_a_star, weighted_a_star, Graph2D, Vertex2D};
+use a_star::stats::{a_star_record, SearchEvent};
+use common::graphs::{Network, Tweaked};
+use common::{is_valid_path, path_cost, Cell, Grid};
+
+use std::hash::{Hash, Hasher};
+
+/// A 4-connected grid that keeps its cells in a flat, row-major list, in
+/// which every step costs the same.
+struct Flat {
+	cells: Vec<Cell>,
+	width: usize,
+}
+
+impl Flat {
+	/// Builds a grid from its rows, in which `#` is a wall.
+	fn parse(rows: &[&str]) -> Self {
+		let cells = rows
+			.iter()
+			.enumerate()
+			.flat_map(|(y, row)| {
+				row.chars().enumerate().map(move |(x, c)| Cell {
+					x,
+					y,
+					is_wall: c == '#',
+				})
+			})
+			.collect();
+		Self {
+			cells,
+			width: rows.first().map_or(0, |row| row.len()),
+		}
+	}
+
+	fn at(&self, x: usize, y: usize) -> &Cell {
+		&self.cells[y * self.width + x]
+	}
+}
+
+impl Graph2D<Cell> for Flat {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		let height = self.cells.len() / self.width;
+		a_star::grid::grid_neighbors_4((cell.x, cell.y), self.width, height)
+			.into_iter()
+			.map(|(x, y)| self.at(x, y))
+			.collect()
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		!from.is_wall && !to.is_wall
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.cells.contains(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		cell.manhattan_distance(other)
+	}
+
+	fn travel_cost(&self, _: &Cell, _: &Cell) -> f64 {
+		1.0
+	}
+}
+
+#[test]
+fn finds_a_straight_path() {
+	let map = Flat::parse(&["....."]);
+	let path = a_star(&map, map.at(0, 0), map.at(4, 0)).unwrap();
+	let expected: Vec<_> = (0..5).map(|x| map.at(x, 0)).collect();
+	assert_eq!(path, expected);
+}
+
+#[test]
+fn unreachable_goal_has_no_path() {
+	let map = Flat::parse(&["..#..", "..#..", "..#.."]);
+	assert_eq!(a_star(&map, map.at(0, 1), map.at(4, 1)), None);
+}
+
+#[test]
+fn wall_goal_has_no_path_even_right_next_to_the_start() {
+	let grid = Grid::parse(&["...", ".#.", "..."]);
+	for &(x, y) in &[(0, 1), (0, 0), (1, 0)] {
+		let (start, goal) = (grid.at(x, y), grid.at(1, 1));
+		assert_eq!(a_star(&grid, start, goal), None);
+		assert_eq!(a_star_reversed(&grid, start, goal), None);
+		assert_eq!(bidirectional_a_star(&grid, start, goal), None);
+		assert_eq!(weighted_a_star(&grid, start, goal, 2.0), None);
+	}
+}
+
+#[test]
+fn start_already_at_the_goal() {
+	let map = Flat::parse(&["...", "...", "..."]);
+	let path = a_star(&map, map.at(1, 1), map.at(1, 1)).unwrap();
+	assert_eq!(path, vec![map.at(1, 1)]);
+}
+
+#[test]
+fn single_step_path() {
+	let map = Flat::parse(&["...", "...", "..."]);
+	let path = a_star(&map, map.at(1, 1), map.at(2, 1)).unwrap();
+	assert_eq!(path, vec![map.at(1, 1), map.at(2, 1)]);
+}
+
+#[test]
+fn routes_around_a_wall() {
+	let map = Flat::parse(&[".....", "..#..", "..#..", "..#..", "....."]);
+	let start = map.at(0, 2);
+	let goal = map.at(4, 2);
+	let path = a_star(&map, start, goal).unwrap();
+	assert_eq!(path[0], start);
+	assert_eq!(path[path.len() - 1], goal);
+	assert!(is_valid_path(&map, &path));
+	assert!(path.iter().all(|cell| !cell.is_wall));
+	// Two rows up or down to get around either end of the wall, four across,
+	// and two back
+	assert!((path_cost(&map, &path) - 8.0).abs() < 1e-9);
+}
+
+#[test]
+fn reversed_path_goes_from_the_goal_to_the_start() {
+	// A single way through, so both searches settle on the same path
+	let grid = Grid::parse(&["..#...", "#.#.#.", "#...#."]);
+	let (start, goal) = (grid.at(0, 0), grid.at(5, 0));
+	let mut path = a_star_reversed(&grid, start, goal).unwrap();
+	assert_eq!(path[0], goal);
+	path.reverse();
+	assert_eq!(path, a_star(&grid, start, goal).unwrap());
+}
+
+#[test]
+fn self_edges_are_ignored() {
+	let grid = Grid::parse(&["....", ".##.", "...."]);
+	// Every cell also claims to neighbor itself, for less than nothing
+	let map = Tweaked::new(&grid)
+		.neighbors(|grid, cell| {
+			let mut neighbors = grid.neighbors(cell);
+			neighbors.push(grid.at(cell.x, cell.y));
+			neighbors
+		})
+		.transversable(|grid, from, to| from == to || grid.path_is_transversable(from, to))
+		.travel_cost(|grid, from, to| if from == to { -1.0 } else { grid.travel_cost(from, to) });
+	let path = a_star(&map, grid.at(0, 1), grid.at(3, 1)).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert!(path.windows(2).all(|step| step[0] != step[1]));
+	assert_eq!(path.len(), 6);
+	let (_, log) = a_star_record(&map, grid.at(0, 1), grid.at(3, 1));
+	let self_relaxed = |event: &SearchEvent| matches!(event, SearchEvent::Relaxed { from, to, .. } if from == to);
+	assert!(!log.events().iter().any(self_relaxed));
+}
+
+/// A cell whose equality only looks at its row, forgetting its column.
+#[derive(Debug)]
+struct RowOnly(Cell);
+
+impl PartialEq for RowOnly {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.y == other.0.y
+	}
+}
+
+impl Hash for RowOnly {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.y.hash(state);
+	}
+}
+
+impl Eq for RowOnly {}
+
+impl Vertex2D for RowOnly {
+	fn coords(&self) -> (f64, f64) {
+		self.0.coords()
+	}
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the vertices at (0.0, 0.0) and (1.0, 0.0) are equal, so they can't be told apart")]
+fn partially_equal_vertices_are_caught_in_debug_builds() {
+	let cell = |x, y| {
+		RowOnly(Cell {
+			x,
+			y,
+			is_wall: false,
+		})
+	};
+	let map = Network::complete(vec![cell(0, 1), cell(0, 0), cell(1, 0)]);
+	a_star(&map, map.at(0), map.at(2));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the vertices at (0.0, 0.0) and (1.0, 0.0) are equal, so they can't be told apart")]
+fn neighbors_equal_to_their_vertex_elsewhere_are_caught_in_debug_builds() {
+	let cell = |x, y| {
+		RowOnly(Cell {
+			x,
+			y,
+			is_wall: false,
+		})
+	};
+	let map = Network::complete(vec![cell(0, 0), cell(1, 0), cell(0, 1)]);
+	a_star(&map, map.at(0), map.at(2));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the neighbor at (7.0, 3.0) of the vertex at (0.0, 0.0) is not part of the graph")]
+fn foreign_neighbors_are_caught_in_debug_builds() {
+	// The only vertex of the graph claims to neighbor a cell it doesn't own
+	let grid = Grid::open(8, 4);
+	let map = Tweaked::new(&grid)
+		.neighbors(|grid, _| vec![grid.at(7, 3)])
+		.has_vertex(|_, cell| (cell.x, cell.y) == (0, 0));
+	a_star(&map, grid.at(0, 0), grid.at(1, 0));
+}
+
+#[test]
+fn indices_map_back_to_the_same_path() {
+	let map = Flat::parse(&[".#...", ".#.#.", "...#."]);
+	let cells = &map.cells;
+	let indices = a_star_indices(&map, cells, 0, 14).unwrap();
+	let path: Vec<_> = indices.iter().map(|&index| &cells[index]).collect();
+	assert_eq!(path.len(), 11);
+	assert_eq!(path[0], &cells[0]);
+	assert_eq!(path[10], &cells[14]);
+	// There is a single way through this maze
+	assert_eq!(Some(path), a_star(&map, &cells[0], &cells[14]));
+}
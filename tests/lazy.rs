@@ -0,0 +1,73 @@
+mod common;
+
+use a_star::lazy::{a_star_lazy, StateGraph};
+use a_star::Vertex2D;
+
+use std::collections::{HashSet, VecDeque};
+
+/// The tiles of a 3×3 sliding puzzle, row by row, `0` being the gap.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Board([u8; 9]);
+
+impl Vertex2D for Board {
+	fn coords(&self) -> (f64, f64) {
+		(0.0, 0.0)
+	}
+}
+
+struct Slides;
+
+impl StateGraph<Board> for Slides {
+	fn moves(&self, board: &Board) -> Vec<Board> {
+		let gap = board.0.iter().position(|&tile| tile == 0).unwrap();
+		let tiles = [
+			(gap % 3 > 0, gap.wrapping_sub(1)),
+			(gap % 3 < 2, gap + 1),
+			(gap >= 3, gap.wrapping_sub(3)),
+			(gap < 6, gap + 3),
+		];
+		let tiles = tiles.iter().filter(|&&(exists, _)| exists);
+		tiles
+			.map(|&(_, tile)| {
+				let mut next = board.clone();
+				next.0.swap(gap, tile);
+				next
+			})
+			.collect()
+	}
+
+	/// Counts the tiles out of place.
+	fn estimate(&self, board: &Board, goal: &Board) -> f64 {
+		let misplaced = board.0.iter().zip(&goal.0).filter(|&(&tile, &other)| tile != 0 && tile != other);
+		misplaced.count() as f64
+	}
+}
+
+/// Returns the fewest moves between two boards, by trying every sequence of
+/// moves, shortest first.
+fn fewest_moves(start: &Board, goal: &Board) -> usize {
+	let mut seen = HashSet::new();
+	let mut queue = VecDeque::from(vec![(start.clone(), 0)]);
+	while let Some((board, moves)) = queue.pop_front() {
+		if board == *goal {
+			return moves;
+		}
+		for next in Slides.moves(&board) {
+			if seen.insert(next.clone()) {
+				queue.push_back((next, moves + 1));
+			}
+		}
+	}
+	unreachable!("the goal can't be reached")
+}
+
+#[test]
+fn scrambled_puzzles_are_solved_in_the_fewest_moves() {
+	let scrambled = Board([4, 1, 3, 7, 2, 6, 0, 5, 8]);
+	let solved = Board([1, 2, 3, 4, 5, 6, 7, 8, 0]);
+	let solution = a_star_lazy(&Slides, scrambled.clone(), &solved).unwrap();
+	assert_eq!((&solution[0], &solution[solution.len() - 1]), (&scrambled, &solved));
+	assert!(solution.windows(2).all(|step| Slides.moves(&step[0]).contains(&step[1])));
+	assert_eq!(solution.len() - 1, fewest_moves(&scrambled, &solved));
+	assert_eq!(solution.len() - 1, 6);
+}
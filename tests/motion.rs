@@ -0,0 +1,94 @@
+mod common;
+
+use a_star::a_star;
+use a_star::motion::{a_star_turn_penalty, turn_sharpness, DriftGraph, TurnPenalty};
+use a_star::Graph2D;
+use common::{is_valid_path, path_cost, Cell, Grid};
+
+#[test]
+fn sharper_turns_are_sharper() {
+	let grid = Grid::open(3, 3);
+	let sharpness = |(x, y)| turn_sharpness(grid.at(0, 1), grid.at(1, 1), grid.at(x, y));
+	assert!(sharpness((2, 1)).abs() < 1e-9);
+	assert!((sharpness((1, 2)) - 1.0).abs() < 1e-9);
+	assert!((sharpness((0, 1)) - 2.0).abs() < 1e-9);
+	assert!(sharpness((2, 2)) < sharpness((1, 2)));
+	assert!(sharpness((0, 2)) > sharpness((1, 2)));
+}
+
+#[test]
+fn penalized_path_swings_wide_instead_of_a_hairpin() {
+	let rows = ["......", "......", "###...", "......", "......"];
+	let map = TurnPenalty::new(Grid::parse(&rows), 4.0);
+	let (start, goal) = (map.at(0, 0), map.at(0, 4));
+	let path = a_star(&map, start, goal).unwrap();
+	assert!(is_valid_path(&map, &path));
+	// Around the end of the wall in two gentle arcs, rather than right angles
+	assert!(path.windows(3).all(|turn| turn_sharpness(turn[0], turn[1], turn[2]) < 0.5));
+	let grid = Grid::parse(&rows);
+	let shortest = a_star(&grid, grid.at(0, 0), grid.at(0, 4)).unwrap();
+	assert!((path_cost(&grid, &shortest) - path_cost(&grid, &path)).abs() < 1e-9);
+}
+
+/// Returns how many times a path changes direction.
+fn turns(path: &[&Cell]) -> usize {
+	path.windows(3).filter(|turn| turn_sharpness(turn[0], turn[1], turn[2]) > 1e-9).count()
+}
+
+/// Returns the cheapest cost of every simple path from `from` to `goal`,
+/// turns included, by trying all of them.
+fn cheapest_by_brute_force<'g>(grid: &'g Grid, path: &mut Vec<&'g Cell>, goal: &Cell, turn_cost: f64) -> f64 {
+	let last = path[path.len() - 1];
+	if last == goal {
+		return path_cost(grid, path) + turns(path) as f64 * turn_cost;
+	}
+	let mut cheapest = f64::INFINITY;
+	for neighbor in grid.neighbors(last) {
+		if path.contains(&neighbor) || !grid.path_is_transversable(last, neighbor) {
+			continue;
+		}
+		path.push(neighbor);
+		cheapest = cheapest.min(cheapest_by_brute_force(grid, path, goal, turn_cost));
+		path.pop();
+	}
+	cheapest
+}
+
+#[test]
+fn turn_penalized_paths_are_the_cheapest_turns_included() {
+	let grid = Grid::parse(&["....", ".##.", "...#", "#..."]);
+	let (start, goal) = (grid.at(0, 0), grid.at(3, 3));
+	let turn_cost = 1.5;
+	let path = a_star_turn_penalty(&grid, start, goal, turn_cost).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	let cost = path_cost(&grid, &path) + turns(&path) as f64 * turn_cost;
+	let cheapest = cheapest_by_brute_force(&grid, &mut vec![start], goal, turn_cost);
+	assert!((cost - cheapest).abs() < 1e-9);
+	// Along an open row, there is no need to turn at all
+	let straight = a_star_turn_penalty(&grid, grid.at(0, 0), grid.at(3, 0), turn_cost).unwrap();
+	assert_eq!(turns(&straight), 0);
+}
+
+#[test]
+fn drifting_agents_tack_across_a_headwind() {
+	let grid = Grid::open(9, 5);
+	let headwind = DriftGraph::new(&grid, (-0.9, 0.0));
+	assert!((headwind.travel_cost(grid.at(1, 2), grid.at(0, 2)) - 1.0 / 1.9).abs() < 1e-9);
+	assert!((headwind.travel_cost(grid.at(0, 2), grid.at(1, 2)) - 10.0).abs() < 1e-9);
+	let (start, goal) = (grid.at(0, 2), grid.at(8, 2));
+	let straight = a_star(&grid, start, goal).unwrap();
+	let path = a_star(&headwind, start, goal).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	// Crossing the wind at an angle beats heading straight into it
+	assert!(path[1..path.len() - 1].iter().all(|cell| cell.y != 2));
+	assert!(path_cost(&headwind, &path) < path_cost(&headwind, &straight) / 2.0);
+	let across = DriftGraph::new(&grid, (0.0, 0.5));
+	assert_eq!(a_star(&across, start, goal), Some(straight));
+}
+
+#[test]
+#[should_panic(expected = "the drift outruns the agents")]
+fn drifts_as_fast_as_the_agents_are_refused() {
+	let grid = Grid::open(3, 3);
+	DriftGraph::new(&grid, (0.6, -0.8));
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use a_star::oracle::{a_star_with_oracle, DistanceOracle, Landmarks};
+use a_star::stats::a_star_with_stats;
+use a_star::a_star;
+use common::graphs::Tweaked;
+use common::{path_cost, Grid};
+
+#[test]
+fn landmarks_guide_the_search_around_a_wall() {
+	let mut grid = Grid::open(40, 40);
+	// A wall down most of the grid, which the heuristic of the grid ignores
+	for y in 0..36 {
+		grid.set_wall(20, y, true);
+	}
+	let (start, goal) = (grid.at(5, 5), grid.at(35, 5));
+	let landmarks = Landmarks::new(&grid, &[grid.at(0, 0), grid.at(39, 0), grid.at(39, 39), grid.at(0, 39)]);
+	let guided = a_star_with_oracle(&grid, start, goal, &landmarks).unwrap();
+	let plain = a_star(&grid, start, goal).unwrap();
+	assert!((path_cost(&grid, &guided) - path_cost(&grid, &plain)).abs() < 1e-9);
+	let guided_map = Tweaked::new(&grid).heuristic(|_, cell, other| landmarks.distance(cell, other));
+	let expanded = a_star_with_stats(&guided_map, start, goal).1.expanded;
+	let plain_expanded = a_star_with_stats(&grid, start, goal).1.expanded;
+	assert!(expanded * 3 < plain_expanded);
+}
+
+/// Only compiles for types that can be shared between threads.
+fn assert_sync<T: Sync>(_: &T) {}
+
+#[test]
+fn landmarks_can_be_shared_between_threads() {
+	let grid = Grid::open(8, 8);
+	let landmarks = Landmarks::new(&grid, &[grid.at(0, 0)]);
+	assert_sync(&landmarks);
+	let (shared, map) = (&landmarks, &grid);
+	std::thread::scope(|scope| {
+		let handles: Vec<_> = (0..4)
+			.map(|y| scope.spawn(move || shared.distance(map.at(0, 0), map.at(7, y))))
+			.collect();
+		for (y, handle) in handles.into_iter().enumerate() {
+			assert_eq!(handle.join().unwrap(), landmarks.distance(grid.at(0, 0), grid.at(7, y)));
+		}
+	});
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn batches_of_queries_match_serial_ones() {
+	let mut grid = Grid::open(30, 30);
+	for y in 0..25 {
+		grid.set_wall(15, y, true);
+	}
+	let landmarks = Landmarks::new(&grid, &[grid.at(0, 0), grid.at(29, 0), grid.at(29, 29), grid.at(0, 29)]);
+	let queries: Vec<_> = (0..64)
+		.map(|i| (grid.at(i % 15, (i * 7) % 30), grid.at(29 - i % 10, (i * 11) % 30)))
+		.collect();
+	let serial: Vec<_> = queries
+		.iter()
+		.map(|&(start, goal)| a_star_with_oracle(&grid, start, goal, &landmarks))
+		.collect();
+	assert_eq!(landmarks.par_query_batch(&grid, &queries), serial);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use a_star::flood::path_clearance;
+use a_star::heuristic::Admissible;
+use a_star::motion::turn_sharpness;
+use a_star::{a_star, a_star_admissible};
+use common::{path_cost, test_grids, Cell, Grid};
+
+/// What searching a grid from its bottom left to its top right comes up with:
+/// the length of the path, a checksum of its cells, its cost, the cost of the
+/// path the octile heuristic finds, and the total clearance and turns along
+/// the path.
+type Outcome = (usize, u64, f64, f64, f64, f64);
+
+/// The outcomes on the shared grids, the same whether the crate is built
+/// with the standard library or without it, on top of `libm`.
+const OUTCOMES: [(&str, Outcome); 4] = [
+	("spikes", (92, 884_308_274_200_911_543, 100.941_125_497, 100.941_125_497, 122.620_844_288, 13.272_077_939)),
+	("hill", (62, 2_716_919_247_210_061_847, 67.627_416_998, 67.627_416_998, 123.763_206_084, 3.636_038_969)),
+	("l_shape", (47, 16_170_788_790_667_413_066, 49.727_922_061, 49.727_922_061, 120.828_427_125, 3.050_252_532)),
+	("random", (46, 10_095_200_317_986_140_758, 49.142_135_624, 49.142_135_624, 55.870_057_685, 8.393_398_282)),
+];
+
+fn checksum(path: &[&Cell]) -> u64 {
+	path.iter()
+		.fold(0, |sum, cell| sum.wrapping_mul(31).wrapping_add((cell.x * 64 + cell.y) as u64))
+}
+
+fn outcome(grid: &Grid) -> Outcome {
+	let (start, goal) = (grid.at(0, 19), grid.at(37, 1));
+	let path = a_star(grid, start, goal).unwrap();
+	let octile = a_star_admissible(grid, start, goal, &Admissible::octile()).unwrap();
+	let clearance = path_clearance(grid, &path).iter().sum();
+	let turns = path.windows(3).map(|cells| turn_sharpness(cells[0], cells[1], cells[2])).sum();
+	let cost = path_cost(grid, &path);
+	(path.len(), checksum(&path), cost, path_cost(grid, &octile), clearance, turns)
+}
+
+#[test]
+fn searches_come_up_with_the_same_on_every_build() {
+	let grids = [test_grids::spikes(), test_grids::hill(), test_grids::l_shape(), test_grids::random(3, 0.25)];
+	for (grid, &(name, expected)) in grids.iter().zip(&OUTCOMES) {
+		let (len, sum, cost, octile, clearance, turns) = outcome(grid);
+		assert_eq!((len, sum), (expected.0, expected.1), "on {}", name);
+		let reals = [(cost, expected.2), (octile, expected.3), (clearance, expected.4), (turns, expected.5)];
+		for &(real, expected) in &reals {
+			assert!((real - expected).abs() < 1e-8, "{} instead of {} on {}", real, expected, name);
+		}
+	}
+}
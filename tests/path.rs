@@ -0,0 +1,141 @@
+mod common;
+
+use a_star::a_star_annotated;
+use a_star::path::{
+	path_corners, path_deviation, path_follow_velocity, path_throughput, path_to_turtle, remaining_cost, simplify_path,
+};
+use common::{Cell, Grid};
+
+fn cells<'g>(grid: &'g Grid, coords: &[(usize, usize)]) -> Vec<&'g Cell> {
+	coords.iter().map(|&(x, y)| grid.at(x, y)).collect()
+}
+
+#[test]
+fn corners_collapse_straight_runs() {
+	let grid = Grid::open(5, 5);
+	let path = cells(&grid, &[(0, 0), (1, 0), (2, 0), (3, 0), (3, 1), (3, 2), (3, 3)]);
+	assert_eq!(path_corners(&path), cells(&grid, &[(0, 0), (3, 0), (3, 3)]));
+	let diagonal = cells(&grid, &[(0, 0), (1, 1), (2, 2), (2, 3)]);
+	assert_eq!(path_corners(&diagonal), cells(&grid, &[(0, 0), (2, 2), (2, 3)]));
+}
+
+#[test]
+fn corners_of_trivial_paths() {
+	let grid = Grid::open(2, 2);
+	assert!(path_corners::<Cell>(&[]).is_empty());
+	assert_eq!(path_corners(&cells(&grid, &[(1, 1)])), cells(&grid, &[(1, 1)]));
+	let waiting = cells(&grid, &[(0, 0), (1, 0), (1, 0), (1, 1)]);
+	assert_eq!(path_corners(&waiting), cells(&grid, &[(0, 0), (1, 0), (1, 1)]));
+}
+
+#[test]
+fn velocity_points_along_a_straight_path() {
+	let grid = Grid::open(6, 3);
+	let path = cells(&grid, &[(0, 1), (1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]);
+	let (x, y) = path_follow_velocity(&path, (1.0, 1.0), 0.5, 2.0);
+	assert!((x - 0.5).abs() < 1e-9 && y.abs() < 1e-9);
+	// Slightly off the path, it steers back onto it
+	let (x, y) = path_follow_velocity(&path, (1.0, 1.5), 0.5, 2.0);
+	assert!(x > 0.0 && y < 0.0);
+	// Near the end, it slows down to stop on it
+	let (x, y) = path_follow_velocity(&path, (4.8, 1.0), 0.5, 2.0);
+	assert!((x - 0.2).abs() < 1e-9 && y.abs() < 1e-9);
+}
+
+#[test]
+fn velocity_anticipates_a_turn() {
+	let grid = Grid::open(6, 6);
+	let path = cells(&grid, &[(0, 0), (5, 0), (5, 5)]);
+	let (x, y) = path_follow_velocity(&path, (4.0, 0.0), 1.0, 2.0);
+	assert!(x > 0.0 && y > 0.0);
+	assert!((x.hypot(y) - 1.0).abs() < 1e-9);
+	assert_eq!(path_follow_velocity::<Cell>(&[], (4.0, 0.0), 1.0, 2.0), (0.0, 0.0));
+}
+
+#[test]
+fn deviation_points_at_where_the_agent_strayed_from() {
+	let grid = Grid::open(6, 6);
+	let path = cells(&grid, &[(0, 0), (3, 0), (3, 3), (5, 5)]);
+	assert_eq!(path_deviation(&path, (1.5, 0.2), 0.5), None);
+	assert_eq!(path_deviation(&path, (3.1, 2.0), 0.5), None);
+	// Pushed off the second leg
+	assert_eq!(path_deviation(&path, (4.0, 1.5), 0.5), Some(1));
+	assert_eq!(path_deviation(&path, (5.0, 3.0), 0.5), Some(2));
+	assert_eq!(path_deviation(&path[..1], (1.0, 0.0), 0.5), Some(0));
+	assert_eq!(path_deviation::<Cell>(&[], (1.0, 0.0), 0.5), None);
+}
+
+#[test]
+fn remaining_cost_goes_down_to_zero_along_the_path() {
+	let grid = Grid::parse(&[".....", "..#..", "....."]);
+	let path = a_star_annotated(&grid, grid.at(0, 1), grid.at(4, 1)).unwrap();
+	let total = path[path.len() - 1].1;
+	assert!((total - (2.0 + 2.0 * 2f64.sqrt())).abs() < 1e-9);
+	assert_eq!(remaining_cost(&path, 0), total);
+	let middle = path.len() / 2;
+	assert!((remaining_cost(&path, middle) - (total - path[middle].1)).abs() < 1e-9);
+	assert!(remaining_cost(&path, middle) > 0.0 && remaining_cost(&path, middle) < total);
+	assert_eq!(remaining_cost(&path, path.len() - 1), 0.0);
+	assert_eq!(remaining_cost(&path, path.len()), 0.0);
+}
+
+#[test]
+fn turtle_turns_a_right_angle_at_the_corner() {
+	let grid = Grid::open(4, 4);
+	let path = cells(&grid, &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (1, 1)]);
+	let turtle = path_to_turtle(&path);
+	assert_eq!(turtle.len(), 5);
+	assert_eq!(turtle[..2], [(0.0, 1.0), (0.0, 1.0)]);
+	// From heading along x to heading along y
+	let (turn, distance) = turtle[2];
+	assert!((turn - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+	assert_eq!(distance, 1.0);
+	assert_eq!(turtle[3], (0.0, 1.0));
+	let (turn, distance) = turtle[4];
+	assert!((turn - 3.0 * std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+	assert!((distance - 2f64.sqrt()).abs() < 1e-9);
+	assert!(path_to_turtle(&path[..1]).is_empty());
+}
+
+#[test]
+fn staircases_simplify_down_to_their_dominant_corners() {
+	let grid = Grid::open(7, 7);
+	let mut coords = vec![(0, 0), (1, 0), (2, 0), (3, 0), (3, 1), (4, 1), (5, 1), (6, 1)];
+	coords.extend((2..7).map(|y| (6, y)));
+	let path = cells(&grid, &coords);
+	assert_eq!(simplify_path(&path, 2), cells(&grid, &[(0, 0), (6, 6)]));
+	assert_eq!(simplify_path(&path, 3), cells(&grid, &[(0, 0), (6, 1), (6, 6)]));
+	for max_points in 2..coords.len() {
+		let simplified = simplify_path(&path, max_points);
+		assert!(simplified.len() <= max_points);
+		assert_eq!((simplified[0], simplified[simplified.len() - 1]), (path[0], path[path.len() - 1]));
+	}
+	// With room to spare, only straight runs are collapsed
+	assert_eq!(simplify_path(&path, coords.len()), path_corners(&path));
+}
+
+#[test]
+fn simplified_paths_never_exceed_tiny_budgets() {
+	let grid = Grid::open(5, 5);
+	let path = cells(&grid, &[(0, 0), (1, 1), (2, 1), (3, 3)]);
+	let step = cells(&grid, &[(0, 0), (1, 1)]);
+	for path in &[&path[..], &step[..]] {
+		assert!(simplify_path(path, 0).is_empty());
+		assert_eq!(simplify_path(path, 1), vec![path[0]]);
+		assert_eq!(simplify_path(path, 2), vec![path[0], path[path.len() - 1]]);
+	}
+	assert_eq!(simplify_path(&path[..1], 2), vec![path[0]]);
+}
+
+#[test]
+fn throughput_fits_agents_spaced_along_the_path() {
+	let grid = Grid::open(5, 5);
+	// Six steps long, four of them straight and two diagonal
+	let path = cells(&grid, &[(0, 0), (1, 0), (2, 0), (3, 1), (4, 2), (4, 3), (4, 4)]);
+	let length = 4.0 + 2.0 * 2f64.sqrt();
+	assert_eq!(path_throughput(&grid, &path, 1.0), 7);
+	assert_eq!(path_throughput(&grid, &path, length / 3.0), 4);
+	assert_eq!(path_throughput(&grid, &path, length + 1.0), 1);
+	assert_eq!(path_throughput(&grid, &path[..1], 1.0), 1);
+	assert_eq!(path_throughput::<_, Cell>(&grid, &[], 1.0), 0);
+}
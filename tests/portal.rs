@@ -0,0 +1,58 @@
+mod common;
+
+use a_star::flood::reachable_within;
+use a_star::portal::PortalGraph;
+use a_star::{a_star, bidirectional_a_star, Graph2D};
+use common::{is_valid_path, path_cost, Grid};
+
+#[test]
+fn portals_cut_paths_short() {
+	let grid = Grid::parse(&["..........", "#########.", ".........."]);
+	let (start, goal) = (grid.at(0, 0), grid.at(0, 2));
+	let walking = a_star(&grid, start, goal).unwrap();
+	let portals = PortalGraph::new(&grid, vec![(grid.at(1, 0), grid.at(1, 2), 0.5)]);
+	let path = a_star(&portals, start, goal).unwrap();
+	assert_eq!(path, vec![start, grid.at(1, 0), grid.at(1, 2), goal]);
+	assert!(is_valid_path(&portals, &path));
+	assert!((path_cost(&portals, &path) - 2.5).abs() < 1e-9);
+	assert!(path_cost(&portals, &path) < path_cost(&grid, &walking));
+	// Portals only lead one way, even when searching backwards
+	let back = bidirectional_a_star(&portals, goal, start).unwrap();
+	assert!((path_cost(&portals, &back) - path_cost(&grid, &walking)).abs() < 1e-9);
+}
+
+#[test]
+fn heuristic_accounts_for_portals() {
+	let grid = Grid::open(10, 1);
+	let portals = PortalGraph::new(&grid, vec![(grid.at(1, 0), grid.at(8, 0), 1.0)]);
+	assert!((portals.heuristic(grid.at(0, 0), grid.at(9, 0)) - 3.0).abs() < 1e-9);
+	assert!((portals.heuristic(grid.at(9, 0), grid.at(0, 0)) - 9.0).abs() < 1e-9);
+}
+
+#[test]
+fn chained_portals_keep_the_heuristic_admissible() {
+	let grid = Grid::open(20, 1);
+	let portals = vec![(grid.at(1, 0), grid.at(10, 0), 1.0), (grid.at(11, 0), grid.at(18, 0), 1.0)];
+	let portals = PortalGraph::new(&grid, portals);
+	let (start, goal) = (grid.at(0, 0), grid.at(19, 0));
+	// Through both portals, one right after the other
+	let cheapest = |cell| reachable_within(&portals, cell, f64::INFINITY)[&goal];
+	assert!((cheapest(start) - 5.0).abs() < 1e-9);
+	assert!(grid.cells().all(|cell| portals.heuristic(cell, goal) <= cheapest(cell) + 1e-9));
+	let path = a_star(&portals, start, goal).unwrap();
+	assert!(is_valid_path(&portals, &path));
+	assert!((path_cost(&portals, &path) - cheapest(start)).abs() < 1e-9);
+}
+
+#[test]
+fn portals_dearer_than_the_step_they_duplicate_are_passed_up() {
+	let grid = Grid::open(5, 1);
+	let portals = vec![(grid.at(1, 0), grid.at(2, 0), 5.0), (grid.at(3, 0), grid.at(4, 0), 0.5)];
+	let portals = PortalGraph::new(&grid, portals);
+	assert!((portals.travel_cost(grid.at(1, 0), grid.at(2, 0)) - 1.0).abs() < 1e-9);
+	assert!((portals.travel_cost(grid.at(3, 0), grid.at(4, 0)) - 0.5).abs() < 1e-9);
+	assert_eq!(portals.neighbors(grid.at(1, 0)).len(), 2);
+	assert_eq!(portals.predecessors(grid.at(2, 0)).len(), 2);
+	let path = a_star(&portals, grid.at(0, 0), grid.at(4, 0)).unwrap();
+	assert!((path_cost(&portals, &path) - 3.5).abs() < 1e-9);
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use a_star::roadmap::RoadmapGraph;
+use a_star::{a_star, bidirectional_a_star};
+use common::Cell;
+
+fn roadmap(coords: &[(usize, usize)], radius: f64) -> RoadmapGraph<Cell> {
+	let points = coords.iter().map(|&(x, y)| Cell { x, y, is_wall: false }).collect();
+	RoadmapGraph::new(points, radius)
+}
+
+#[test]
+fn too_few_nearest_neighbors_miss_the_way_across_a_gap() {
+	let coords = [(0, 0), (1, 0), (0, 1), (3, 0), (4, 0)];
+	let points = roadmap(&coords, 2.5);
+	let (start, goal) = (&points.points()[0], &points.points()[4]);
+	let path = a_star(&points, start, goal).unwrap();
+	let expected: Vec<_> = [0, 1, 3, 4].iter().map(|&i| &points.points()[i]).collect();
+	assert_eq!(path, expected);
+	let four = roadmap(&coords, 2.5).with_k_nearest(4);
+	let (start, goal) = (&four.points()[0], &four.points()[4]);
+	assert_eq!(a_star(&four, start, goal).unwrap().len(), 4);
+	assert_eq!(bidirectional_a_star(&four, start, goal).unwrap().len(), 4);
+	// The second point's nearest is the first, so the gap is never crossed
+	let one = roadmap(&coords, 2.5).with_k_nearest(1);
+	let (start, goal) = (&one.points()[0], &one.points()[4]);
+	assert!(a_star(&one, start, goal).is_none());
+	assert!(bidirectional_a_star(&one, start, goal).is_none());
+}
@@ -0,0 +1,693 @@
+mod common;
+
+use a_star::{
+	a_star, a_star_adaptive, a_star_annotated, a_star_expansion_order, a_star_filtered, a_star_guided, a_star_heap,
+	a_star_max_hops, a_star_seeded, a_star_time_expanded, a_star_with_frontier_snapshots, a_star_with_options,
+	a_star_with_turn_restrictions, all_optimal_paths, bidirectional_a_star, detour_cost, disjoint_backup_path,
+	group_paths, ida_star, minimax_step_path, weighted_a_star, weighted_a_star_with_gap, widest_path, Graph2D,
+	SearchOptions,
+};
+use a_star::cost::CostStack;
+use a_star::motion::turn_sharpness;
+use common::graphs::{Network, Tweaked};
+use common::{is_valid_path, path_cost, test_grids, Cell, Grid};
+
+#[test]
+fn max_hops_reaches_goal_exactly_at_the_cap() {
+	let grid = Grid::open(5, 3);
+	let path = a_star_max_hops(&grid, grid.at(0, 1), grid.at(4, 1), 4).unwrap();
+	assert_eq!(path.len(), 5);
+	assert_eq!(path[0], grid.at(0, 1));
+	assert_eq!(path[4], grid.at(4, 1));
+}
+
+#[test]
+fn max_hops_rejects_goal_one_hop_beyond_the_cap() {
+	let grid = Grid::open(5, 3);
+	assert!(a_star_max_hops(&grid, grid.at(0, 1), grid.at(4, 1), 3).is_none());
+}
+
+#[test]
+fn max_hops_takes_a_costlier_route_when_the_cheapest_is_too_long() {
+	let cell = |x, y| Cell { x, y, is_wall: false };
+	// A short but dear way into (2, 0), a long but cheap one, and two more
+	// hops from there to the goal
+	let vertices = vec![cell(0, 0), cell(1, 0), cell(2, 0), cell(3, 0), cell(4, 0), cell(0, 1), cell(1, 1), cell(2, 1)];
+	let edges = vec![(0, 1), (1, 2), (2, 3), (3, 4), (0, 5), (5, 6), (6, 7), (7, 2)];
+	let network = Network::new(vertices, edges);
+	let map = Tweaked::new(&network).travel_cost(|network, from, to: &Cell| {
+		if (to.x, to.y) == (1, 0) {
+			10.0
+		} else {
+			network.travel_cost(from, to)
+		}
+	});
+	let (start, goal) = (network.at(0), network.at(4));
+	assert_eq!(a_star_max_hops(&map, start, goal, 6).unwrap().len(), 7);
+	let path = a_star_max_hops(&map, start, goal, 4).unwrap();
+	let expected: Vec<_> = (0..5).map(|index| network.at(index)).collect();
+	assert_eq!(path, expected);
+	assert!(a_star_max_hops(&map, start, goal, 3).is_none());
+}
+
+#[test]
+fn seeded_search_starts_from_the_nearest_seed() {
+	let grid = Grid::parse(&["........", ".####...", "........"]);
+	let (near, far, goal) = (grid.at(7, 0), grid.at(0, 1), grid.at(5, 2));
+	let seeds = [far, near];
+	let free = |cell: &Cell| if seeds.contains(&cell) { Some(0.0) } else { None };
+	let path = a_star_seeded(&grid, &seeds, goal, free).unwrap();
+	assert_eq!(path[0], near);
+	let nearest = a_star(&grid, near, goal).unwrap();
+	assert!((path_cost(&grid, &path) - path_cost(&grid, &nearest)).abs() < 1e-9);
+	// Getting to the nearest seed in the first place may not be worth it
+	let costly = |cell: &Cell| free(cell).map(|_| if cell == near { 10.0 } else { 0.0 });
+	let path = a_star_seeded(&grid, &seeds, goal, costly).unwrap();
+	assert_eq!(path[0], far);
+	assert!(a_star_seeded(&grid, &seeds, goal, |_| None).is_none());
+}
+
+#[test]
+fn frontier_snapshots_are_taken_every_few_expansions() {
+	let grid = Grid::open(20, 1);
+	let (start, goal) = (grid.at(0, 0), grid.at(19, 0));
+	let (path, snapshots) = a_star_with_frontier_snapshots(&grid, start, goal, 5).unwrap();
+	// Every cell but the goal gets expanded along the way
+	assert_eq!(snapshots.len(), (path.len() - 1) / 5);
+	assert!(snapshots.iter().all(|frontier| !frontier.contains(&goal)));
+	let (_, snapshots) = a_star_with_frontier_snapshots(&grid, start, goal, 0).unwrap();
+	assert!(snapshots.is_empty());
+}
+
+#[test]
+fn expansion_order_starts_at_the_start_and_ends_next_to_the_goal() {
+	let grid = Grid::parse(&["......", ".####.", "......"]);
+	let (start, goal) = (grid.at(0, 1), grid.at(5, 1));
+	let (path, order) = a_star_expansion_order(&grid, start, goal).unwrap();
+	assert_eq!(order[0], start);
+	assert_eq!(order[order.len() - 1], path[path.len() - 2]);
+	assert!(!order.contains(&goal));
+	assert!(path.iter().take(path.len() - 1).all(|cell| order.contains(cell)));
+}
+
+#[test]
+fn adaptive_search_falls_back_to_iterative_deepening() {
+	let grid = Grid::parse(&["......", ".####.", "......", "##.###", "......"]);
+	let (start, goal) = (grid.at(0, 4), grid.at(5, 0));
+	let optimal = path_cost(&grid, &a_star(&grid, start, goal).unwrap());
+	for &mem_limit_nodes in &[3, 1000] {
+		let path = a_star_adaptive(&grid, start, goal, mem_limit_nodes).unwrap();
+		assert!(is_valid_path(&grid, &path));
+		assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+		assert!((path_cost(&grid, &path) - optimal).abs() < 1e-9);
+	}
+	let path = ida_star(&grid, start, goal).unwrap();
+	assert!((path_cost(&grid, &path) - optimal).abs() < 1e-9);
+	let walled = Grid::parse(&["..#.."]);
+	assert!(a_star_adaptive(&walled, walled.at(0, 0), walled.at(4, 0), 1).is_none());
+}
+
+#[test]
+fn all_optimal_paths_returns_both_routes_around_a_pillar() {
+	let grid = Grid::parse(&["...", ".#.", "..."]);
+	let start = grid.at(0, 1);
+	let goal = grid.at(2, 1);
+	let paths = all_optimal_paths(&grid, start, goal, 10);
+	assert_eq!(paths.len(), 2);
+	for path in &paths {
+		assert_eq!(path[0], start);
+		assert_eq!(path[path.len() - 1], goal);
+		assert!(is_valid_path(&grid, path));
+		assert!((path_cost(&grid, path) - 4.0).abs() < 1e-9);
+	}
+	assert_ne!(paths[0], paths[1]);
+}
+
+#[test]
+fn all_optimal_paths_respects_the_cap() {
+	let grid = Grid::parse(&["...", ".#.", "..."]);
+	let paths = all_optimal_paths(&grid, grid.at(0, 1), grid.at(2, 1), 1);
+	assert_eq!(paths.len(), 1);
+}
+
+#[test]
+fn all_optimal_paths_walks_around_zero_cost_cycles() {
+	let grid = Grid::parse(&["...."]);
+	// The two middle cells are joined by a free edge
+	let is_free = |from: &Cell, to: &Cell| from.x.min(to.x) == 1 && from.x.max(to.x) == 2;
+	let map = Tweaked::new(&grid)
+		.heuristic(|_, _, _| 0.0)
+		.travel_cost(|grid, from, to| if is_free(from, to) { 0.0 } else { grid.travel_cost(from, to) });
+	let (start, goal) = (grid.at(0, 0), grid.at(3, 0));
+	let paths = all_optimal_paths(&map, start, goal, 10);
+	assert_eq!(paths, vec![vec![start, grid.at(1, 0), grid.at(2, 0), goal]]);
+	assert!((path_cost(&map, &paths[0]) - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn annotated_costs_grow_along_the_path_and_match_each_step() {
+	let grid = Grid::parse(&[".....", ".###.", "....."]);
+	let annotated = a_star_annotated(&grid, grid.at(0, 1), grid.at(4, 1)).unwrap();
+	let path: Vec<_> = annotated.iter().map(|&(cell, _)| cell).collect();
+	assert_eq!(annotated[0].1, 0.0);
+	for step in annotated.windows(2) {
+		assert!(step[1].1 > step[0].1);
+		let cost = step[1].1 - step[0].1;
+		assert!((cost - grid.travel_cost(step[0].0, step[1].0)).abs() < 1e-9);
+	}
+	let total = annotated[annotated.len() - 1].1;
+	assert!((total - path_cost(&grid, &path)).abs() < 1e-9);
+}
+
+#[test]
+fn widest_path_takes_the_wider_corridor() {
+	let widths = ["11111", "31113", "33333"];
+	let width_at = |cell: &Cell| widths[cell.y].as_bytes()[cell.x] - b'0';
+	let grid = Grid::parse(&widths);
+	let start = grid.at(0, 1);
+	let goal = grid.at(4, 1);
+	let path = widest_path(&grid, start, goal, |_, to| f64::from(width_at(to))).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert_eq!(path[0], start);
+	assert_eq!(path[path.len() - 1], goal);
+	assert!(path.iter().all(|cell| width_at(cell) == 3));
+	let shortest = a_star(&grid, start, goal).unwrap();
+	assert!(shortest.iter().any(|cell| width_at(cell) == 1));
+}
+
+#[test]
+fn time_expanded_search_waits_or_detours_around_a_moving_obstacle() {
+	let grid = Grid::parse(&[".......", "......."]);
+	let start = grid.at(0, 1);
+	let goal = grid.at(6, 1);
+	// Something crosses the straight route right as the agent gets there
+	let is_blocked = |cell: &Cell, tick| (cell.x, cell.y) == (3, 1) && (2..=4).contains(&tick);
+	let waiting = a_star_time_expanded(&grid, start, goal, is_blocked, 20, Some(0.1)).unwrap();
+	assert!(waiting.windows(2).any(|step| step[0] == step[1]));
+	assert!(waiting.iter().all(|cell| cell.y == 1));
+	let moving = a_star_time_expanded(&grid, start, goal, is_blocked, 20, None).unwrap();
+	assert!(moving.windows(2).all(|step| step[0] != step[1]));
+	assert!(is_valid_path(&grid, &moving));
+	assert!(path_cost(&grid, &moving) > path_cost(&grid, &waiting));
+	for path in &[waiting, moving] {
+		assert!(path.iter().enumerate().all(|(tick, cell)| !is_blocked(cell, tick)));
+	}
+}
+
+#[test]
+fn guided_search_follows_the_corridor_over_the_shortest_route() {
+	let grid = Grid::parse(&[".......", ".#####.", ".......", "......."]);
+	let start = grid.at(0, 0);
+	let goal = grid.at(6, 0);
+	// A coarse route that goes around the bottom of the wall
+	let corridor: Vec<_> = (0..3)
+		.map(|y| (0, y))
+		.chain((0..=6).map(|x| (x, 3)))
+		.chain((0..3).rev().map(|y| (6, y)))
+		.map(|(x, y)| grid.at(x, y))
+		.collect();
+	let path = a_star_guided(&grid, start, goal, &corridor, 20.0).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert!(path.iter().any(|cell| cell.y == 3));
+	let shortest = a_star(&grid, start, goal).unwrap();
+	assert!(shortest.iter().all(|cell| cell.y == 0));
+}
+
+#[test]
+fn weighted_gap_is_between_one_and_epsilon() {
+	let grid = Grid::parse(&[
+		"..........",
+		"...#......",
+		"...#..###.",
+		"...#....#.",
+		"...####.#.",
+		"........#.",
+	]);
+	let start = grid.at(0, 5);
+	let goal = grid.at(9, 0);
+	let optimal = path_cost(&grid, &all_optimal_paths(&grid, start, goal, 1)[0]);
+	for &epsilon in &[1.0, 1.5, 3.0] {
+		let (path, gap) = weighted_a_star_with_gap(&grid, start, goal, epsilon).unwrap();
+		assert!(is_valid_path(&grid, &path));
+		assert!(gap >= 1.0 && gap <= epsilon);
+		assert!(path_cost(&grid, &path) <= gap * optimal + 1e-9);
+	}
+	let path = weighted_a_star(&grid, start, goal, 1.0).unwrap();
+	assert!((path_cost(&grid, &path) - optimal).abs() < 1e-9);
+}
+
+#[test]
+fn filtered_search_never_moves_north() {
+	let grid = Grid::parse(&[".....", "###..", ".....", "..###", "....."]);
+	let no_north = |from: &Cell, to: &Cell| to.y >= from.y;
+	let path = a_star_filtered(&grid, grid.at(0, 0), grid.at(4, 4), no_north).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert!(path.windows(2).all(|step| no_north(step[0], step[1])));
+	// Leaving a pocket that only opens to the north is then impossible
+	let grid = Grid::parse(&["...", "#.#", "###"]);
+	assert!(a_star(&grid, grid.at(1, 1), grid.at(0, 0)).is_some());
+	assert!(a_star_filtered(&grid, grid.at(1, 1), grid.at(0, 0), no_north).is_none());
+}
+
+/// A grid with a conveyor belt in its center cell, which makes it cheap to
+/// carry on east after entering it from the west.
+struct Conveyor(Grid);
+
+impl Graph2D<Cell> for Conveyor {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		self.0.neighbors(cell)
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		self.0.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.0.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.0.heuristic(cell, other) * 0.1
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		self.0.travel_cost(from, to)
+	}
+
+	fn travel_cost_with_context(&self, previous: Option<&Cell>, from: &Cell, to: &Cell) -> f64 {
+		let on_belt = (from.x, from.y) == (1, 1) && to.x == 2;
+		match previous {
+			Some(previous) if on_belt && previous.x == 0 && previous.y == 1 => 0.1,
+			_ => self.travel_cost(from, to),
+		}
+	}
+}
+
+#[test]
+fn travel_cost_depends_on_the_incoming_direction() {
+	let map = Conveyor(Grid::open(3, 3));
+	let (west, north, center, east) = (map.0.at(0, 1), map.0.at(1, 0), map.0.at(1, 1), map.0.at(2, 1));
+	let from_west = map.travel_cost_with_context(Some(west), center, east);
+	let from_north = map.travel_cost_with_context(Some(north), center, east);
+	assert!(from_west < from_north);
+	// The search hands the parent over, so riding the belt is cheap
+	let path = a_star_annotated(&map, west, east).unwrap();
+	assert_eq!(path.len(), 3);
+	assert!((path[2].1 - 1.1).abs() < 1e-9);
+}
+
+/// A grid whose heuristic overshoots by a constant, even at the goal, and
+/// which counts how many vertices got expanded.
+struct Overshooting {
+	grid: Grid,
+	expansions: std::cell::Cell<usize>,
+}
+
+impl Graph2D<Cell> for Overshooting {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		self.expansions.set(self.expansions.get() + 1);
+		self.grid.neighbors(cell)
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		self.grid.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.grid.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.grid.heuristic(cell, other) + 3.0
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		self.grid.travel_cost(from, to)
+	}
+}
+
+#[test]
+fn reaching_the_goal_stops_the_search_before_popping_it() {
+	let map = Overshooting {
+		grid: Grid::parse(&["......", "..#...", "..#...", "..#...", "..#...", "......"]),
+		expansions: std::cell::Cell::new(0),
+	};
+	let (start, goal) = (map.grid.at(0, 2), map.grid.at(5, 2));
+	let path = a_star(&map, start, goal).unwrap();
+	let early_out = map.expansions.replace(0);
+	// Weighted A* only stops once the goal's own, overshot, score is the best
+	let popped = weighted_a_star(&map, start, goal, 1.0).unwrap();
+	assert!(early_out < map.expansions.get());
+	assert!((path_cost(&map.grid, &path) - path_cost(&map.grid, &popped)).abs() < 1e-9);
+}
+
+/// A grid where stepping east along the top row costs a toll.
+struct Toll(Grid);
+
+impl Graph2D<Cell> for Toll {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		self.0.neighbors(cell)
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		self.0.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.0.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.0.heuristic(cell, other)
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		let toll = if from.y == 0 && to.y == 0 && to.x > from.x { 10.0 } else { 0.0 };
+		self.0.travel_cost(from, to) + toll
+	}
+}
+
+#[test]
+fn goal_seen_through_an_expensive_edge_is_still_reached_optimally() {
+	let map = Toll(Grid::open(3, 2));
+	let grid = &map.0;
+	let path = a_star(&map, grid.at(0, 0), grid.at(2, 0)).unwrap();
+	assert_eq!(path, vec![grid.at(0, 0), grid.at(1, 1), grid.at(2, 0)]);
+}
+
+/// A grid whose top two rows are one-way streets heading east, and in which
+/// heading west costs thrice as much elsewhere.
+struct OneWay(Grid);
+
+impl OneWay {
+	fn allows(from: &Cell, to: &Cell) -> bool {
+		from.y >= 2 || to.x >= from.x
+	}
+}
+
+impl Graph2D<Cell> for OneWay {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		let neighbors = self.0.neighbors(cell).into_iter();
+		neighbors.filter(|neighbor| Self::allows(cell, neighbor)).collect()
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		self.0.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.0.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.0.heuristic(cell, other)
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		let toll = if to.x < from.x { 3.0 } else { 1.0 };
+		self.0.travel_cost(from, to) * toll
+	}
+
+	fn predecessors(&self, cell: &Cell) -> Vec<&Cell> {
+		let predecessors = self.0.neighbors(cell).into_iter();
+		predecessors.filter(|predecessor| Self::allows(predecessor, cell)).collect()
+	}
+}
+
+#[test]
+fn bidirectional_search_follows_one_way_edges() {
+	let map = OneWay(Grid::parse(&["......", "......", "..##..", "......"]));
+	let cells: Vec<_> = (0..4).flat_map(|y| (0..6).map(move |x| (x, y))).collect();
+	for &(start_x, start_y) in &cells {
+		for &(goal_x, goal_y) in &cells {
+			let (start, goal) = (map.0.at(start_x, start_y), map.0.at(goal_x, goal_y));
+			let forward = a_star(&map, start, goal);
+			let both_ways = bidirectional_a_star(&map, start, goal);
+			assert_eq!(forward.is_some(), both_ways.is_some());
+			if let (Some(forward), Some(both_ways)) = (forward, both_ways) {
+				assert_eq!(both_ways[0], start);
+				assert_eq!(both_ways[both_ways.len() - 1], goal);
+				assert!(both_ways.windows(2).all(|step| OneWay::allows(step[0], step[1])));
+				assert!((path_cost(&map, &both_ways) - path_cost(&map, &forward)).abs() < 1e-9);
+			}
+		}
+	}
+}
+
+#[test]
+fn turn_restrictions_take_a_detour_instead_of_turning_back() {
+	// The shortest route from the first junction to the third one doubles back
+	// sharply at the second
+	let junctions = [(0, 0), (4, 0), (1, 1), (5, 2), (3, 3)];
+	let junctions = junctions.iter().map(|&(x, y)| Cell { x, y, is_wall: false }).collect();
+	let roads = Network::new(junctions, vec![(0, 1), (1, 2), (1, 3), (3, 4), (4, 2)]);
+	let (start, goal) = (roads.at(0), roads.at(2));
+	let shortest = a_star(&roads, start, goal).unwrap();
+	assert_eq!(shortest, vec![start, roads.at(1), goal]);
+	let turning_back = |previous: &Cell, cell: &Cell, next: &Cell| turn_sharpness(previous, cell, next) > 1.0;
+	let path = a_star_with_turn_restrictions(&roads, start, goal, turning_back).unwrap();
+	assert_eq!(path, vec![start, roads.at(1), roads.at(3), roads.at(4), goal]);
+	assert!(path.windows(3).all(|turn| !turning_back(turn[0], turn[1], turn[2])));
+	assert!(path_cost(&roads, &path) > path_cost(&roads, &shortest));
+}
+
+#[test]
+fn ties_are_broken_the_same_way_on_every_run() {
+	let grid = Grid::parse(&["....", ".##.", "...."]);
+	let (start, goal) = (grid.at(0, 1), grid.at(3, 1));
+	let first = a_star(&grid, start, goal).unwrap();
+	assert_eq!(first[1], grid.at(0, 0));
+	for _ in 0..32 {
+		assert_eq!(a_star(&grid, start, goal).unwrap(), first);
+	}
+}
+
+/// Counts how many times a path changes direction.
+fn turns(path: &[&Cell]) -> usize {
+	let step = |from: &Cell, to: &Cell| (to.x.wrapping_sub(from.x), to.y.wrapping_sub(from.y));
+	let turns = path.windows(3).filter(|moves| step(moves[0], moves[1]) != step(moves[1], moves[2]));
+	turns.count()
+}
+
+#[test]
+fn goal_attraction_smooths_out_a_detour() {
+	let grid = Grid::parse(&["...#.....", "...#.....", "...#.....", "...#.....", ".........", "........."]);
+	let (start, goal) = (grid.at(0, 0), grid.at(8, 4));
+	let plain = a_star_with_options(&grid, start, goal, &SearchOptions::default()).unwrap();
+	assert_eq!(plain, a_star(&grid, start, goal).unwrap());
+	let options = SearchOptions {
+		goal_attraction: 0.2,
+		..SearchOptions::default()
+	};
+	let attracted = a_star_with_options(&grid, start, goal, &options).unwrap();
+	assert!(is_valid_path(&grid, &attracted));
+	assert!(turns(&attracted) < turns(&plain));
+	assert!(path_cost(&grid, &attracted) >= path_cost(&grid, &plain) - 1e-9);
+}
+
+#[test]
+fn paths_thousands_of_steps_long_do_not_overflow_the_stack() {
+	let grid = Grid::open(2000, 2000);
+	let (start, goal) = (grid.at(0, 1000), grid.at(1999, 1000));
+	let path = a_star(&grid, start, goal).unwrap();
+	assert_eq!(path.len(), 2000);
+	assert!(is_valid_path(&grid, &path));
+	assert_eq!(ida_star(&grid, start, goal).unwrap(), path);
+	let reversed = a_star(&grid, goal, start).unwrap();
+	assert!((path_cost(&grid, &reversed) - 1999.0).abs() < 1e-9);
+}
+
+// Without the standard library, the set to avoid is a `hashbrown` one
+#[cfg(feature = "std")]
+#[test]
+fn soft_avoid_crosses_cheaply_but_detours_around_a_high_penalty() {
+	use a_star::a_star_soft_avoid;
+	use std::collections::HashSet;
+
+	let grid = Grid::open(7, 5);
+	let avoid: HashSet<_> = (0..4).map(|y| grid.at(3, y)).collect();
+	let (start, goal) = (grid.at(0, 0), grid.at(6, 0));
+	let crossing = a_star_soft_avoid(&grid, start, goal, &avoid, 1.0).unwrap();
+	assert_eq!(crossing, a_star(&grid, start, goal).unwrap());
+	assert!(crossing.iter().any(|cell| avoid.contains(cell)));
+	let detour = a_star_soft_avoid(&grid, start, goal, &avoid, 10.0).unwrap();
+	assert!(is_valid_path(&grid, &detour));
+	assert!(detour.iter().all(|cell| !avoid.contains(cell)));
+	assert!(detour.contains(&grid.at(3, 4)));
+}
+
+#[test]
+fn backup_paths_share_no_cells_with_the_best_one() {
+	let grid = Grid::parse(&[".......", ".#####.", "......."]);
+	let (start, goal) = (grid.at(0, 1), grid.at(6, 1));
+	let (primary, backup) = disjoint_backup_path(&grid, start, goal).unwrap();
+	let backup = backup.unwrap();
+	assert_eq!(primary, a_star(&grid, start, goal).unwrap());
+	assert!(is_valid_path(&grid, &backup));
+	assert_eq!((backup[0], backup[backup.len() - 1]), (start, goal));
+	assert!(backup[1..backup.len() - 1].iter().all(|cell| !primary.contains(cell)));
+	// A single corridor has no room for a backup
+	let grid = Grid::parse(&["#####", ".....", "#####"]);
+	let (primary, backup) = disjoint_backup_path(&grid, grid.at(0, 1), grid.at(4, 1)).unwrap();
+	assert_eq!(primary.len(), 5);
+	assert_eq!(backup, None);
+}
+
+#[test]
+fn detours_cost_the_difference_between_the_cheapest_paths() {
+	let open = Grid::parse(&[".....", ".....", "....."]);
+	let walled = Grid::parse(&[".....", "..#..", "....."]);
+	let (start, goal) = (walled.at(0, 1), walled.at(4, 1));
+	// Going around the wall takes two diagonal steps instead of straight ones
+	let detour = detour_cost(&walled, &open, start, goal);
+	assert!((detour - (2.0 * 2.0_f64.sqrt() - 2.0)).abs() < 1e-9);
+	let cut_off = Grid::parse(&["..#..", "..#..", "..#.."]);
+	assert_eq!(detour_cost(&cut_off, &open, start, goal), f64::INFINITY);
+	assert_eq!(detour_cost(&open, &open, start, goal), 0.0);
+}
+
+/// A line of four vertices, `S`, `A`, `B` and `G`, with a shortcut from `S` to
+/// `B` that is costlier than going through `A`, and a heuristic that is
+/// admissible but wildly overestimates how far `A` is compared to `B`.
+struct Inconsistent {
+	vertices: Vec<Cell>,
+}
+
+impl Inconsistent {
+	const EDGES: [(usize, usize, f64); 4] = [(0, 1, 1.0), (1, 2, 1.0), (0, 2, 3.0), (2, 3, 3.0)];
+	const ESTIMATES: [f64; 4] = [5.0, 4.0, 0.0, 0.0];
+
+	fn new() -> Self {
+		let vertices = (0..4).map(|x| Cell { x, y: 0, is_wall: false }).collect();
+		Self { vertices }
+	}
+}
+
+impl Graph2D<Cell> for Inconsistent {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		let edges = Self::EDGES.iter().filter(|&&(from, _, _)| from == cell.x);
+		edges.map(|&(_, to, _)| &self.vertices[to]).collect()
+	}
+
+	fn path_is_transversable(&self, _: &Cell, _: &Cell) -> bool {
+		true
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.vertices.contains(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, _: &Cell) -> f64 {
+		Self::ESTIMATES[cell.x]
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		let edge = Self::EDGES.iter().find(|&&(start, end, _)| (start, end) == (from.x, to.x));
+		edge.map_or(f64::INFINITY, |&(_, _, cost)| cost)
+	}
+}
+
+#[test]
+fn reopening_keeps_paths_optimal_under_inconsistent_heuristics() {
+	let graph = Inconsistent::new();
+	let (start, goal) = (&graph.vertices[0], &graph.vertices[3]);
+	let reopening = a_star_with_options(&graph, start, goal, &SearchOptions::default()).unwrap();
+	assert_eq!(path_cost(&graph, &reopening), 5.0);
+	let options = SearchOptions {
+		allow_reopening: false,
+		..SearchOptions::default()
+	};
+	let closed = a_star_with_options(&graph, start, goal, &options).unwrap();
+	assert_eq!(closed, vec![start, &graph.vertices[2], goal]);
+	assert_eq!(path_cost(&graph, &closed), 6.0);
+}
+
+#[test]
+fn vertices_behind_impassable_edges_are_not_taken_for_expanded() {
+	// The goal can only be reached from the top middle, which the start
+	// neighbors at an infinite cost
+	let grid = Grid::parse(&["...", "..#"]);
+	let map = Tweaked::new(&grid).travel_cost(|grid, from, to: &Cell| {
+		if ((from.x, from.y), (to.x, to.y)) == ((0, 0), (1, 0)) {
+			f64::INFINITY
+		} else {
+			grid.travel_cost(from, to)
+		}
+	});
+	let options = SearchOptions {
+		allow_reopening: false,
+		..SearchOptions::default()
+	};
+	let path = a_star_with_options(&map, grid.at(0, 0), grid.at(2, 0), &options).unwrap();
+	assert_eq!(path, vec![grid.at(0, 0), grid.at(1, 1), grid.at(1, 0), grid.at(2, 0)]);
+}
+
+#[test]
+fn minimax_paths_avoid_the_single_hardest_move() {
+	let grid = Grid::parse(&[".....", ".###.", "....."]);
+	// Climbing onto the middle of the top row is thrice as hard as any other
+	// move, but still cheaper than going all the way around
+	let climb = |_: &Cell, to: &Cell, cost: f64| if (to.x, to.y) == (2, 0) { cost * 3.0 } else { cost };
+	let terrain = CostStack::new(&grid).with(climb);
+	let (start, goal) = (grid.at(0, 0), grid.at(4, 0));
+	let shortest = a_star(&terrain, start, goal).unwrap();
+	assert!(shortest.contains(&grid.at(2, 0)));
+	let path = minimax_step_path(&terrain, start, goal).unwrap();
+	assert!(is_valid_path(&grid, &path));
+	assert!(path.windows(2).all(|step| terrain.travel_cost(step[0], step[1]) < 2.0));
+	assert!(path_cost(&terrain, &path) > path_cost(&terrain, &shortest));
+}
+
+#[test]
+fn grouped_units_take_turns_through_a_door() {
+	let grid = Grid::parse(&["...#..", "...#..", "......", "...#..", "...#.."]);
+	let starts = [grid.at(0, 1), grid.at(0, 2), grid.at(0, 3)];
+	let goals = [grid.at(5, 1), grid.at(5, 2), grid.at(5, 3)];
+	let paths: Vec<_> = group_paths(&grid, &starts, &goals).into_iter().map(Option::unwrap).collect();
+	for (path, (&start, &goal)) in paths.iter().zip(starts.iter().zip(&goals)) {
+		assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+		assert!(path.windows(2).all(|step| step[0] == step[1] || grid.path_is_transversable(step[0], step[1])));
+	}
+	// Units that arrived stay where they are
+	let at = |path: &[&Cell], tick: usize| {
+		let cell = path[tick.min(path.len() - 1)];
+		(cell.x, cell.y)
+	};
+	let ticks = paths.iter().map(Vec::len).max().unwrap();
+	for tick in 0..ticks {
+		for (index, path) in paths.iter().enumerate() {
+			assert!(paths[index + 1..].iter().all(|other| at(path, tick) != at(other, tick)));
+		}
+	}
+}
+
+#[test]
+fn grouped_units_that_cannot_get_past_the_others_have_no_path() {
+	// The first unit parks for good where the second one is headed
+	let grid = Grid::open(5, 1);
+	let starts = [grid.at(0, 0), grid.at(4, 0)];
+	let goals = [grid.at(2, 0), grid.at(2, 0)];
+	let paths = group_paths(&grid, &starts, &goals);
+	assert_eq!(paths, vec![a_star(&grid, starts[0], goals[0]), None]);
+}
+
+#[test]
+#[should_panic(expected = "every start needs a goal of its own")]
+fn grouped_units_need_as_many_goals_as_starts() {
+	let grid = Grid::open(5, 1);
+	group_paths(&grid, &[grid.at(0, 0), grid.at(4, 0)], &[grid.at(2, 0)]);
+}
+
+#[test]
+fn heaps_pop_nodes_in_the_same_order_as_scanning() {
+	for seed in 0..8 {
+		let grid = test_grids::random(seed, 0.3);
+		for &(start, goal) in &[((0, 19), (49, 0)), ((0, 0), (37, 1)), ((25, 10), (49, 19))] {
+			let (start, goal) = (grid.at(start.0, start.1), grid.at(goal.0, goal.1));
+			assert_eq!(a_star_heap(&grid, start, goal), a_star(&grid, start, goal));
+		}
+	}
+	// Walled off, so that both give up only once everything was expanded
+	let grid = Grid::from_fn(30, 30, |x, y| x + y == 55);
+	assert_eq!(a_star_heap(&grid, grid.at(0, 0), grid.at(29, 29)), None);
+}
@@ -0,0 +1,25 @@
+mod common;
+
+use a_star::sight::{furthest_visible, line_of_sight};
+use common::Grid;
+
+#[test]
+fn lines_of_sight_are_blocked_by_walls() {
+	let grid = Grid::parse(&["......", "..#...", "......", "......"]);
+	assert!(line_of_sight(&grid, grid.at(0, 2), grid.at(5, 3)));
+	assert!(!line_of_sight(&grid, grid.at(0, 0), grid.at(5, 3)));
+	assert!(line_of_sight(&grid, grid.at(5, 3), grid.at(0, 3)));
+	assert!(!line_of_sight(&grid, grid.at(0, 0), grid.at(4, 2)));
+	assert!(!line_of_sight(&grid, grid.at(0, 1), grid.at(5, 1)));
+}
+
+#[test]
+fn furthest_visible_stops_right_before_the_wall_hides_the_path() {
+	let grid = Grid::parse(&["......", "..#...", "......", "......"]);
+	let path: Vec<_> = [(0, 0), (1, 0), (2, 0), (3, 0), (3, 1), (3, 2), (3, 3)]
+		.iter()
+		.map(|&(x, y)| grid.at(x, y))
+		.collect();
+	assert_eq!(furthest_visible(&grid, path[0], &path, 1), 3);
+	assert_eq!(furthest_visible(&grid, path[3], &path, 4), 6);
+}
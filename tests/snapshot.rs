@@ -0,0 +1,29 @@
+mod common;
+
+use a_star::snapshot::SnapshotGraph;
+use a_star::{a_star, a_star_filtered};
+use common::{Cell, Grid};
+
+#[test]
+fn walls_proposed_during_a_search_show_up_in_the_next_one() {
+	let mut map = SnapshotGraph::new(Grid::parse(&[".....", "..#..", "....."]));
+	let (start, goal) = ((0, 1), (4, 1));
+	// Close off the top row as soon as the search gets near it
+	let closing = |_: &Cell, to: &Cell| {
+		if to.y == 0 && map.pending() == 0 {
+			map.propose(|grid: &mut Grid| (0..5).for_each(|x| grid.set_wall(x, 0, true)));
+		}
+		true
+	};
+	let path = a_star_filtered(&map, map.at(start.0, start.1), map.at(goal.0, goal.1), closing).unwrap();
+	let before = path.len();
+	assert_eq!(map.pending(), 1);
+	assert_eq!(map.commit(), 1);
+	assert!((0..5).all(|x| map.at(x, 0).is_wall));
+	let path = a_star(&map, map.at(start.0, start.1), map.at(goal.0, goal.1)).unwrap();
+	assert!(path.iter().all(|cell| cell.y != 0));
+	assert_eq!(path.len(), before);
+	// Changes can still be made directly while nothing is searching
+	map.set_wall(2, 2, true);
+	assert!(a_star(&map, map.at(start.0, start.1), map.at(goal.0, goal.1)).is_none());
+}
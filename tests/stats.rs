@@ -0,0 +1,92 @@
+mod common;
+
+use a_star::heuristic::octile;
+use a_star::stats::{a_star_record, a_star_with_stats, replay_matches, search_efficiency, SearchEvent, SearchLog};
+use a_star::Graph2D;
+use common::graphs::Tweaked;
+use common::{Cell, Grid};
+
+#[test]
+fn better_heuristics_are_more_efficient() {
+	let grid = Grid::open(10, 5);
+	// Without walls, the octile distance is exact
+	let perfect = Tweaked::new(&grid).heuristic(octile);
+	let none = Tweaked::new(&grid).heuristic(|_, _, _| 0.0);
+	let efficiency = |map: &Tweaked<Grid>| search_efficiency(map, grid.at(0, 2), grid.at(9, 2));
+	assert!((efficiency(&perfect) - 1.0).abs() < 1e-9);
+	assert!(efficiency(&none) > 3.0);
+	let walled = Grid::parse(&["..#.."]);
+	assert!(search_efficiency(&walled, walled.at(0, 0), walled.at(4, 0)).is_infinite());
+}
+
+#[test]
+fn stats_count_expansions_with_or_without_a_path() {
+	let grid = Grid::open(5, 1);
+	let (path, stats) = a_star_with_stats(&grid, grid.at(0, 0), grid.at(4, 0));
+	assert_eq!(path.unwrap().len(), 5);
+	assert_eq!(stats.expanded, 4);
+	assert_eq!(stats.seen, 5);
+	let walled = Grid::parse(&["..#.."]);
+	let (path, stats) = a_star_with_stats(&walled, walled.at(0, 0), walled.at(4, 0));
+	assert!(path.is_none());
+	assert_eq!(stats.expanded, 2);
+}
+
+#[test]
+fn recorded_searches_replay_the_same_steps() {
+	let grid = Grid::parse(&["......", ".##...", "...#..", "......"]);
+	let (start, goal) = (grid.at(0, 0), grid.at(5, 3));
+	let (path, log) = a_star_record(&grid, start, goal);
+	let expanded = |log: &SearchLog| {
+		let events = log.events().iter();
+		events.filter(|event| matches!(event, SearchEvent::Expanded { .. })).count()
+	};
+	assert_eq!(
+		log.events()[0],
+		SearchEvent::Expanded {
+			coords: (0.0, 0.0),
+			g_score: 0.0,
+			f_score: 5.0,
+		}
+	);
+	assert_eq!(expanded(&log), a_star_with_stats(&grid, start, goal).1.expanded);
+	for _ in 0..8 {
+		let (fresh_path, fresh_log) = a_star_record(&grid, start, goal);
+		assert_eq!(fresh_path, path);
+		assert!(replay_matches(&log, &fresh_log));
+	}
+	let (_, elsewhere) = a_star_record(&grid, start, grid.at(5, 0));
+	assert!(!replay_matches(&log, &elsewhere));
+}
+
+#[test]
+fn recorded_progress_follows_every_expansion() {
+	let grid = Grid::open(12, 3);
+	let (_, log) = a_star_record(&grid, grid.at(0, 1), grid.at(11, 1));
+	let steps: Vec<_> = log.events().iter().filter(|event| !matches!(event, SearchEvent::Relaxed { .. })).collect();
+	let progress: Vec<_> = steps
+		.chunks(2)
+		.map(|pair| match pair {
+			[SearchEvent::Expanded { .. }, SearchEvent::Progress(progress)] => *progress,
+			_ => panic!("{:?} is not an expansion followed by its progress", pair),
+		})
+		.collect();
+	assert_eq!(progress[0], 0.0);
+	assert!(progress[progress.len() - 1] > 0.9);
+	assert!(progress.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn dead_ends_are_counted_and_searched_past() {
+	let grid = Grid::open(5, 3);
+	// A single cell is a trap, which can be walked into but not out of
+	let trapped = Tweaked::new(&grid)
+		.transversable(|grid, from: &Cell, to| (from.x, from.y) != (2, 1) && grid.path_is_transversable(from, to));
+	let (start, goal) = (grid.at(0, 1), grid.at(4, 1));
+	let (path, stats) = a_star_with_stats(&trapped, start, goal);
+	let path = path.unwrap();
+	assert!(!path.contains(&grid.at(2, 1)));
+	assert_eq!(path.len(), 5);
+	assert_eq!(stats.dead_ends, 1);
+	assert_eq!(a_star_with_stats(&grid, start, goal).1.dead_ends, 0);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use a_star::a_star;
+use a_star::stream::{a_star_stream, StreamEvent};
+use common::Grid;
+
+use std::sync::mpsc::channel;
+use std::thread;
+
+#[test]
+fn streamed_searches_end_with_the_path_found() {
+	let grid = Grid::parse(&["......", ".####.", "......"]);
+	let (start, goal) = (grid.at(0, 1), grid.at(5, 1));
+	let (tx, rx) = channel();
+	let map = &grid;
+	let events: Vec<_> = thread::scope(|scope| {
+		scope.spawn(move || a_star_stream(map, start, goal, &tx));
+		rx.iter().collect()
+	});
+	let expanded = events.iter().filter(|event| matches!(event, StreamEvent::Expanded(_)));
+	let sizes = events.iter().filter(|event| matches!(event, StreamEvent::FrontierSize(_)));
+	assert!(expanded.count() > 0);
+	assert_eq!(events[0], StreamEvent::Expanded(start));
+	assert_eq!(events.len() % 3, 1);
+	assert_eq!(sizes.count(), events.len() / 3);
+	assert_eq!(events.last(), Some(&StreamEvent::Found(a_star(&grid, start, goal).unwrap())));
+}
+
+#[test]
+fn streamed_searches_without_a_path_fail() {
+	let grid = Grid::parse(&["..#..", "..#..", "..#.."]);
+	let (tx, rx) = channel();
+	a_star_stream(&grid, grid.at(0, 0), grid.at(4, 2), &tx);
+	drop(tx);
+	assert_eq!(rx.iter().last(), Some(StreamEvent::Failed));
+}
+
+#[test]
+fn streamed_progress_goes_from_zero_to_almost_one() {
+	let grid = Grid::open(12, 3);
+	let (tx, rx) = channel();
+	a_star_stream(&grid, grid.at(0, 1), grid.at(11, 1), &tx);
+	drop(tx);
+	let progress: Vec<_> = rx
+		.iter()
+		.filter_map(|event| match event {
+			StreamEvent::Progress(progress) => Some(progress),
+			_ => None,
+		})
+		.collect();
+	assert_eq!(progress[0], 0.0);
+	assert!(progress[progress.len() - 1] > 0.9);
+	assert!(progress.iter().all(|progress| (0.0..=1.0).contains(progress)));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use a_star::a_star;
+use common::test_grids::{self, HEIGHT, WIDTH};
+use common::Grid;
+
+fn walls(grid: &Grid) -> usize {
+	grid.cells().filter(|cell| cell.is_wall).count()
+}
+
+#[test]
+fn named_grids_have_their_walls() {
+	assert_eq!(walls(&test_grids::spikes()), 145);
+	assert_eq!(walls(&test_grids::hill()), 48);
+	assert_eq!(walls(&test_grids::l_shape()), 74);
+	for grid in &[test_grids::spikes(), test_grids::hill(), test_grids::l_shape()] {
+		assert_eq!((grid.width(), grid.height()), (WIDTH, HEIGHT));
+		assert!(a_star(grid, grid.at(0, 19), grid.at(37, 1)).is_some());
+	}
+}
+
+#[test]
+fn random_grids_are_the_same_for_the_same_seed() {
+	let grid = test_grids::random(7, 0.3);
+	let walls_at = |grid: &Grid| grid.cells().map(|cell| cell.is_wall).collect::<Vec<_>>();
+	assert_eq!(walls_at(&grid), walls_at(&test_grids::random(7, 0.3)));
+	assert_ne!(walls_at(&grid), walls_at(&test_grids::random(8, 0.3)));
+	assert_eq!(walls(&test_grids::random(7, 0.0)), 0);
+	assert_eq!(walls(&test_grids::random(7, 1.0)), WIDTH * HEIGHT - 4);
+	let density = walls(&grid) as f64 / (WIDTH * HEIGHT) as f64;
+	assert!((0.25..0.35).contains(&density));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use a_star::ticked::TickedSearch;
+use a_star::{a_star, Graph2D};
+use common::{is_valid_path, path_cost, Cell, Grid};
+
+use std::cell::RefCell;
+use std::task::Poll;
+
+/// An edge between the cells at two coordinates.
+type Edge = ((usize, usize), (usize, usize));
+
+/// A grid some of whose edges can be cut off, even while it is searched.
+struct Cuttable {
+	grid: Grid,
+	cut: RefCell<Vec<Edge>>,
+}
+
+impl Cuttable {
+	fn cut(&self, from: &Cell, to: &Cell) {
+		self.cut.borrow_mut().push(((from.x, from.y), (to.x, to.y)));
+	}
+}
+
+impl Graph2D<Cell> for Cuttable {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		self.grid.neighbors(cell)
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		let edge = ((from.x, from.y), (to.x, to.y));
+		!self.cut.borrow().contains(&edge) && self.grid.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.grid.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.grid.heuristic(cell, other)
+	}
+
+	fn travel_cost(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.grid.travel_cost(cell, other)
+	}
+}
+
+/// Advances a search a couple of vertices at a time until it is over,
+/// returning its outcome and how many ticks it took.
+fn run_to_completion<'m>(search: &mut TickedSearch<'m, Cuttable, Cell>) -> (Option<Vec<&'m Cell>>, usize) {
+	let mut ticks = 1;
+	loop {
+		if let Poll::Ready(path) = search.advance(2) {
+			return (path, ticks);
+		}
+		ticks += 1;
+	}
+}
+
+#[test]
+fn ticked_searches_ignore_changes_far_away() {
+	let map = Cuttable {
+		grid: Grid::parse(&["........", ".######.", "........", "........"]),
+		cut: RefCell::new(Vec::new()),
+	};
+	let (start, goal) = (map.grid.at(0, 0), map.grid.at(7, 2));
+	let mut search = TickedSearch::new(&map, start, goal);
+	assert_eq!(search.advance(2), Poll::Pending);
+	map.cut(map.grid.at(0, 3), map.grid.at(1, 3));
+	search.edge_changed(map.grid.at(0, 3), map.grid.at(1, 3));
+	let (path, ticks) = run_to_completion(&mut search);
+	assert!(ticks > 1);
+	let path = path.unwrap();
+	assert!(is_valid_path(&map, &path));
+	assert_eq!(path_cost(&map, &path), path_cost(&map, &a_star(&map, start, goal).unwrap()));
+	assert_eq!(search.advance(2), Poll::Ready(Some(path)));
+}
+
+#[test]
+fn ticked_searches_route_around_edges_cut_under_them() {
+	let map = Cuttable {
+		grid: Grid::parse(&["........", ".######.", "........"]),
+		cut: RefCell::new(Vec::new()),
+	};
+	let (start, goal) = (map.grid.at(0, 0), map.grid.at(7, 2));
+	let mut search = TickedSearch::new(&map, start, goal);
+	assert_eq!(search.advance(3), Poll::Pending);
+	// The way along the top row is cut off right after the search took it
+	map.cut(map.grid.at(1, 0), map.grid.at(2, 0));
+	search.edge_changed(map.grid.at(1, 0), map.grid.at(2, 0));
+	let path = run_to_completion(&mut search).0.unwrap();
+	assert!(is_valid_path(&map, &path));
+	assert!(path.iter().any(|cell| cell.y == 2 && cell.x < 4));
+	assert_eq!(path_cost(&map, &path), path_cost(&map, &a_star(&map, start, goal).unwrap()));
+}
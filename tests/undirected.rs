@@ -0,0 +1,60 @@
+mod common;
+
+use a_star::undirected::Undirected;
+use a_star::{a_star, Graph2D};
+use common::{Cell, Grid};
+
+/// A grid in which heading left costs thrice as much as heading right, and
+/// the leftmost column can only be left, never entered.
+struct Slanted(Grid);
+
+impl Graph2D<Cell> for Slanted {
+	fn neighbors(&self, cell: &Cell) -> Vec<&Cell> {
+		let neighbors = self.0.neighbors(cell).into_iter();
+		neighbors.filter(|neighbor| neighbor.x > 0).collect()
+	}
+
+	fn path_is_transversable(&self, from: &Cell, to: &Cell) -> bool {
+		to.x > 0 && self.0.path_is_transversable(from, to)
+	}
+
+	fn has_vertex(&self, cell: &Cell) -> bool {
+		self.0.has_vertex(cell)
+	}
+
+	fn heuristic(&self, cell: &Cell, other: &Cell) -> f64 {
+		self.0.heuristic(cell, other)
+	}
+
+	fn travel_cost(&self, from: &Cell, to: &Cell) -> f64 {
+		let cost = self.0.travel_cost(from, to);
+		if to.x < from.x {
+			cost * 3.0
+		} else {
+			cost
+		}
+	}
+
+	fn predecessors(&self, cell: &Cell) -> Vec<&Cell> {
+		let predecessors = self.0.neighbors(cell).into_iter();
+		predecessors.filter(|_| cell.x > 0).collect()
+	}
+}
+
+#[test]
+fn undirected_graphs_are_symmetric() {
+	let slanted = Slanted(Grid::open(4, 3));
+	let undirected = Undirected::new(&slanted);
+	let grid = &slanted.0;
+	assert_eq!(slanted.travel_cost(grid.at(2, 1), grid.at(1, 1)), 3.0);
+	for cell in grid.cells() {
+		for neighbor in undirected.neighbors(cell) {
+			assert!(undirected.neighbors(neighbor).contains(&cell));
+			assert!(undirected.path_is_transversable(neighbor, cell));
+			assert_eq!(undirected.travel_cost(cell, neighbor), undirected.travel_cost(neighbor, cell));
+		}
+	}
+	assert_eq!(undirected.travel_cost(grid.at(2, 1), grid.at(1, 1)), 1.0);
+	assert_eq!(a_star(&slanted, grid.at(3, 1), grid.at(0, 1)), None);
+	assert_eq!(a_star(&undirected, grid.at(3, 1), grid.at(0, 1)).unwrap().len(), 4);
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use a_star::{a_star, delegate_graph2d, Graph2D};
+use common::{is_valid_path, Cell, Grid};
+
+use std::ops::Deref;
+
+/// A wrapper that adds nothing to the grid it wraps.
+struct Wrapped(Grid);
+
+impl Deref for Wrapped {
+	type Target = Grid;
+	fn deref(&self) -> &Grid {
+		&self.0
+	}
+}
+
+delegate_graph2d!(Wrapped => Cell);
+
+/// A generic wrapper, to make sure parameters are carried over.
+struct Generic<G>(G);
+
+impl<G> Deref for Generic<G> {
+	type Target = G;
+	fn deref(&self) -> &G {
+		&self.0
+	}
+}
+
+delegate_graph2d!(impl<G> Generic<G> => Cell);
+
+#[test]
+fn delegated_wrappers_search_like_their_inner_graph() {
+	let wrapped = Wrapped(Grid::parse(&["....", ".##.", "...."]));
+	let path = a_star(&wrapped, wrapped.at(0, 1), wrapped.at(3, 1)).unwrap();
+	assert_eq!(path.len(), 6);
+	assert!(is_valid_path(&wrapped.0, &path));
+	let generic = Generic(Grid::parse(&["....", ".##.", "...."]));
+	let path = a_star(&generic, generic.at(0, 1), generic.at(3, 1)).unwrap();
+	assert_eq!(path.len(), 6);
+	assert_eq!(
+		generic.travel_cost(generic.at(0, 0), generic.at(1, 1)),
+		generic.0.travel_cost(generic.at(0, 0), generic.at(1, 1))
+	);
+}